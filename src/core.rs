@@ -1,29 +1,39 @@
 const MIN_REGION_SIZE: Rectangle = Rectangle { w: 20, h: 20 };
+const FLOAT_CASCADE: i64 = 32;
+const EDGE_TOLERANCE: i64 = 2;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ErrorKind {
     UnknownRegion,
     UnknownWorkspace,
     UnknownMonitor,
     InvalidRegion,
     NoAdjacentRegions,
+    NoHistory,
+    InvalidMonitor,
 }
 
 pub type Result<T> = std::result::Result<T, ErrorKind>;
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+/// Rectangles are ordered by width, then height.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Rectangle {
     pub w: u64,
     pub h: u64,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+/// Positions are ordered by `x`, then `y`. For reading order, see `Workspace::regions_sorted`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Position {
     pub x: i64,
     pub y: i64,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Direction {
     Up,
     Down,
@@ -31,32 +41,140 @@ pub enum Direction {
     Right,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// Which way the Y axis grows. Workspaces always use `Down`, with `Workspace::region_in_convention` and
+/// `Workspace::region_from_convention` converting for display servers using `Up`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum YAxis {
+    /// Y grows downwards from the top of the workspace, so `pos` is the top left corner of a region.
+    Down,
+    /// Y grows upwards from the bottom of the workspace, so `pos` is the bottom left corner of a region.
+    Up,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Region {
     pub size: Rectangle,
     pub pos: Position,
     pub float: bool,
+    pub fullscreen: Option<(Rectangle, Position)>,
+    /// Size limits of the region itself, such as from a window's size hints, enforced by the edge setters on top of
+    /// the minimum size they are given.
+    pub min: Option<Rectangle>,
+    pub max: Option<Rectangle>,
+    /// The tab group the region belongs to, as assigned by `Workspace::group_as_tabs`. Tiled regions in the same group
+    /// occupy the same space, with the first of them taking part in tiling on behalf of the rest.
+    pub tab_group: Option<u32>,
+    /// The stacking order of a floating region, with higher values drawn above lower ones.
+    pub z: u32,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Gaps {
+    pub inner: u64,
+    pub outer: u64,
+}
+
+/// How far floating regions may be moved past the edges of their workspace.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FloatClamp {
+    /// Floating regions must lie entirely within the workspace.
+    Inside,
+    /// At least this many pixels of a floating region must remain within the workspace on each axis.
+    Partial(u64),
+}
+
+/// Which region receives the leftover pixel when splitting a region doesn't divide evenly.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RoundBias {
+    /// The existing region being split keeps the leftover pixel. This is the default.
+    Sibling,
+    /// The newly created region receives the leftover pixel.
+    New,
+}
+
+/// A problem with the tiled layout of a workspace, as reported by `Workspace::layout_issues`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LayoutIssue {
+    /// The tiled regions at these indices overlap.
+    Overlap(usize, usize),
+    /// This area of the workspace isn't covered by any tiled region.
+    Gap(Rectangle, Position),
+    /// The tiled region at this index extends past the workspace bounds.
+    OutOfBounds(usize),
+}
+
+/// A read-only description of a region, as exported by `Workspace::describe`.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RegionInfo {
+    pub index: usize,
+    pub rect: Rectangle,
+    pub pos: Position,
+    pub float: bool,
 }
 
+/// A stable handle on a workspace within a `Manager`, unlike its index in `workspaces`. Workspaces created outside
+/// a manager have the id 0, which `Manager::create_workspace` never assigns.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WorkspaceId(pub u64);
+
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Workspace {
+    pub id: WorkspaceId,
+    pub monitor: Option<usize>,
     pub size: Rectangle,
     pub regions: Vec<Region>,
+    pub min_region_size: Rectangle,
+    pub gaps: Gaps,
+    pub float_clamp: FloatClamp,
+    pub round_bias: RoundBias,
+    pub focused_region: Option<usize>,
+    /// Space reserved along the top, bottom, left and right edges of the workspace, such as for panels and docks.
+    /// Tiled regions are laid out within the remaining `bounds`.
+    pub struts: (u64, u64, u64, u64),
+    pub history_depth: usize,
+    /// Snapshots of the regions along with the geometry kept by `maximize_region` at the time.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    undo_history: Vec<(Vec<Region>, Option<Vec<Region>>)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    redo_history: Vec<(Vec<Region>, Option<Vec<Region>>)>,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    maximized: Option<Vec<Region>>,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Monitor {
     pub size: Rectangle,
     pub pos: Position,
 }
 
 #[derive(Clone, Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Manager {
     pub workspaces: Vec<Workspace>,
     pub monitors: Vec<Monitor>,
+    pub focused_workspace: Option<usize>,
+    next_workspace_id: u64,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Resize {
     Top(i64),
     Bottom(i64),
@@ -68,6 +186,22 @@ pub enum Resize {
     BottomRight(i64, i64),
 }
 
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ErrorKind::UnknownRegion => "unknown region",
+            ErrorKind::UnknownWorkspace => "unknown workspace",
+            ErrorKind::UnknownMonitor => "unknown monitor",
+            ErrorKind::InvalidRegion => "invalid region geometry",
+            ErrorKind::NoAdjacentRegions => "no adjacent regions",
+            ErrorKind::NoHistory => "no history to restore",
+            ErrorKind::InvalidMonitor => "invalid monitor",
+        })
+    }
+}
+
+impl std::error::Error for ErrorKind {}
+
 impl Rectangle {
     pub fn new(width: u64, height: u64) -> Self {
         Self {
@@ -75,21 +209,147 @@ impl Rectangle {
             h: height,
         }
     }
+
+    /// Scale both dimensions by `factor`, rounding to the nearest pixel.
+    pub fn scale(&self, factor: f64) -> Self {
+        Self::new(
+            (self.w as f64 * factor).round() as u64,
+            (self.h as f64 * factor).round() as u64,
+        )
+    }
+
+    /// The area in pixels, saturating at `u64::MAX` for very large rectangles.
+    pub fn area(&self) -> u64 {
+        self.w.saturating_mul(self.h)
+    }
+
+    pub fn aspect_ratio(&self) -> f64 {
+        self.w as f64 / self.h as f64
+    }
+}
+
+impl From<(u64, u64)> for Rectangle {
+    fn from((w, h): (u64, u64)) -> Self {
+        Self::new(w, h)
+    }
+}
+
+impl From<Rectangle> for (u64, u64) {
+    fn from(rectangle: Rectangle) -> Self {
+        (rectangle.w, rectangle.h)
+    }
 }
 
 impl Position {
     pub fn new(x: i64, y: i64) -> Self {
         Self { x, y }
     }
+
+    pub fn offset(self, dx: i64, dy: i64) -> Self {
+        Self::new(self.x + dx, self.y + dy)
+    }
+}
+
+impl From<(i64, i64)> for Position {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl From<Position> for (i64, i64) {
+    fn from(position: Position) -> Self {
+        (position.x, position.y)
+    }
+}
+
+impl std::ops::Add for Position {
+    type Output = Self;
+
+    fn add(self, delta: Self) -> Self {
+        self.offset(delta.x, delta.y)
+    }
+}
+
+impl std::ops::Sub for Position {
+    type Output = Self;
+
+    fn sub(self, delta: Self) -> Self {
+        self.offset(-delta.x, -delta.y)
+    }
+}
+
+impl Direction {
+    /// Every direction, in declaration order.
+    pub fn all() -> [Direction; 4] {
+        [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ]
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+
+    /// The axis along which the direction moves.
+    pub fn axis(self) -> Axis {
+        match self {
+            Direction::Up | Direction::Down => Axis::Vertical,
+            Direction::Left | Direction::Right => Axis::Horizontal,
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        self.axis() == Axis::Horizontal
+    }
+
+    pub fn is_vertical(self) -> bool {
+        self.axis() == Axis::Vertical
+    }
+}
+
+impl Axis {
+    pub fn perpendicular(self) -> Self {
+        match self {
+            Axis::Horizontal => Axis::Vertical,
+            Axis::Vertical => Axis::Horizontal,
+        }
+    }
 }
 
 impl Region {
+    /// Create a region without checking its size. Regions are expected to be at least the minimum region size,
+    /// as the edge setters refuse to shrink them below it; use `try_new` for sizes that aren't known to be valid.
     pub fn new(size: Rectangle, pos: Position, float: bool) -> Self {
-        Self { size, pos, float }
+        Self {
+            size,
+            pos,
+            float,
+            fullscreen: None,
+            min: None,
+            max: None,
+            tab_group: None,
+            z: 0,
+        }
+    }
+
+    pub fn try_new(size: Rectangle, pos: Position, float: bool) -> Result<Self> {
+        if size.w < MIN_REGION_SIZE.w || size.h < MIN_REGION_SIZE.h {
+            return Err(ErrorKind::InvalidRegion);
+        }
+
+        Ok(Self::new(size, pos, float))
     }
 
     pub fn area(&self) -> u64 {
-        self.size.w * self.size.h
+        self.size.area()
     }
 
     pub fn top(&self) -> i64 {
@@ -108,31 +368,194 @@ impl Region {
         self.pos.x + self.size.w as i64
     }
 
-    pub fn set_top(&mut self, new: i64) -> Result<&mut Self> {
-        if new > self.bottom() - MIN_REGION_SIZE.h as i64 {
+    /// The center of the region, rounded towards the top left for odd dimensions.
+    pub fn center(&self) -> Position {
+        self.pos
+            .offset((self.size.w / 2) as i64, (self.size.h / 2) as i64)
+    }
+
+    /// Move the region so that at least `min_visible` pixels of it remain within `bounds` on each axis, without
+    /// changing its size. Less is kept within `bounds` if either the region or `bounds` is smaller than that, so a
+    /// region larger than `bounds` is moved to cover all of it.
+    pub fn clamp_to(&mut self, bounds: &Region, min_visible: u64) {
+        let visible_w = u64::min(min_visible, u64::min(self.size.w, bounds.size.w)) as i64;
+        let visible_h = u64::min(min_visible, u64::min(self.size.h, bounds.size.h)) as i64;
+        let (w, h) = (self.size.w as i64, self.size.h as i64);
+
+        self.pos = Position::new(
+            self.pos
+                .x
+                .clamp(bounds.left() + visible_w - w, bounds.right() - visible_w),
+            self.pos
+                .y
+                .clamp(bounds.top() + visible_h - h, bounds.bottom() - visible_h),
+        );
+    }
+
+    /// Move the region by `dx` and `dy` without changing its size.
+    pub fn translate(&mut self, dx: i64, dy: i64) {
+        self.pos = self.pos.offset(dx, dy);
+    }
+
+    /// A copy of the region moved by `dx` and `dy`, leaving the region itself unchanged.
+    pub fn translated(mut self, dx: i64, dy: i64) -> Region {
+        self.translate(dx, dy);
+        self
+    }
+
+    /// Split the region in two, returning it shrunk along with a new region on its `direction` side taking `ratio`
+    /// of its width or height, rounded down. `ratio` must lie strictly between 0 and 1, and both regions must be at
+    /// least the minimum region size.
+    pub fn split(self, direction: &Direction, ratio: f64) -> Result<(Region, Region)> {
+        self.split_with(direction, ratio, MIN_REGION_SIZE, RoundBias::Sibling)
+    }
+
+    fn split_with(
+        mut self,
+        direction: &Direction,
+        ratio: f64,
+        min: Rectangle,
+        bias: RoundBias,
+    ) -> Result<(Region, Region)> {
+        let (dimension, min) = if direction.is_vertical() {
+            (self.size.h, min.h)
+        } else {
+            (self.size.w, min.w)
+        };
+        let size = match bias {
+            RoundBias::Sibling => (dimension as f64 * ratio).floor(),
+            RoundBias::New => (dimension as f64 * ratio).ceil(),
+        } as u64;
+
+        if ratio <= 0.0 || ratio >= 1.0 || size < min || dimension - size < min {
+            return Err(ErrorKind::InvalidRegion);
+        }
+
+        let mut region = self;
+
+        match direction {
+            Direction::Up => {
+                region.size.h = size;
+                self.size.h -= size;
+                self.translate(0, size as i64);
+            }
+            Direction::Down => {
+                region.size.h = size;
+                self.size.h -= size;
+                region.translate(0, self.size.h as i64);
+            }
+            Direction::Left => {
+                region.size.w = size;
+                self.size.w -= size;
+                self.translate(size as i64, 0);
+            }
+            Direction::Right => {
+                region.size.w = size;
+                self.size.w -= size;
+                region.translate(self.size.w as i64, 0);
+            }
+        }
+
+        Ok((self, region))
+    }
+
+    /// The coordinate of the edge facing `direction`.
+    pub fn edge(&self, direction: Direction) -> i64 {
+        match direction {
+            Direction::Up => self.top(),
+            Direction::Down => self.bottom(),
+            Direction::Left => self.left(),
+            Direction::Right => self.right(),
+        }
+    }
+
+    /// The start and end coordinates of the region along `axis`.
+    fn extent(&self, axis: Axis) -> (i64, i64) {
+        match axis {
+            Axis::Horizontal => (self.left(), self.right()),
+            Axis::Vertical => (self.top(), self.bottom()),
+        }
+    }
+
+    /// Whether the projections of the regions onto the edge facing `direction` overlap.
+    fn overlaps_across(&self, other: &Region, direction: Direction) -> bool {
+        let axis = direction.axis().perpendicular();
+        let ((start, end), (other_start, other_end)) = (self.extent(axis), other.extent(axis));
+
+        other_start < end && other_end > start
+    }
+
+    /// Whether `pos` lies within the region. The right and bottom edges are exclusive so that adjacent regions
+    /// never both claim the pixels along their shared edge.
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.x >= self.left() && pos.x < self.right() && pos.y >= self.top() && pos.y < self.bottom()
+    }
+
+    /// Whether the two regions occupy the same rectangle, regardless of whether either is floating.
+    pub fn same_geometry(&self, other: &Region) -> bool {
+        self.size == other.size && self.pos == other.pos
+    }
+
+    /// Whether the two regions overlap. Regions that only touch along an edge do not intersect.
+    pub fn intersects(&self, other: &Region) -> bool {
+        self.overlap_area(other) > 0
+    }
+
+    pub fn overlap_area(&self, other: &Region) -> u64 {
+        let w = i64::min(self.right(), other.right()) - i64::max(self.left(), other.left());
+        let h = i64::min(self.bottom(), other.bottom()) - i64::max(self.top(), other.top());
+
+        if w <= 0 || h <= 0 {
+            return 0;
+        }
+
+        (w as u64).saturating_mul(h as u64)
+    }
+
+    /// Check that `height` satisfies both `min` and the region's own limits.
+    fn fit_height(&self, height: i64, min: Rectangle) -> Result<()> {
+        let min = u64::max(min.h, self.min.map_or(0, |own| own.h));
+
+        if height < min as i64 || self.max.is_some_and(|max| height > max.h as i64) {
+            return Err(ErrorKind::InvalidRegion);
+        }
+
+        Ok(())
+    }
+
+    /// Check that `width` satisfies both `min` and the region's own limits.
+    fn fit_width(&self, width: i64, min: Rectangle) -> Result<()> {
+        let min = u64::max(min.w, self.min.map_or(0, |own| own.w));
+
+        if width < min as i64 || self.max.is_some_and(|max| width > max.w as i64) {
             return Err(ErrorKind::InvalidRegion);
         }
 
+        Ok(())
+    }
+
+    /// Move the top edge to `new`, keeping the bottom edge in place. The region must stay at least `min`, such as the
+    /// `min_region_size` of its workspace, as well as within its own `min` and `max` limits. The other edge setters
+    /// work alike.
+    pub fn set_top(&mut self, new: i64, min: Rectangle) -> Result<&mut Self> {
+        self.fit_height(self.bottom() - new, min)?;
+
         self.size.h = (self.bottom() - new) as u64;
         self.pos.y = new;
 
         Ok(self)
     }
 
-    pub fn set_bottom(&mut self, new: i64) -> Result<&mut Self> {
-        if new < self.top() + MIN_REGION_SIZE.h as i64 {
-            return Err(ErrorKind::InvalidRegion);
-        }
+    pub fn set_bottom(&mut self, new: i64, min: Rectangle) -> Result<&mut Self> {
+        self.fit_height(new - self.top(), min)?;
 
         self.size.h = (new - self.top()) as u64;
 
         Ok(self)
     }
 
-    pub fn set_left(&mut self, new: i64) -> Result<&mut Self> {
-        if new > self.right() - MIN_REGION_SIZE.w as i64 {
-            return Err(ErrorKind::InvalidRegion);
-        }
+    pub fn set_left(&mut self, new: i64, min: Rectangle) -> Result<&mut Self> {
+        self.fit_width(self.right() - new, min)?;
 
         self.size.w = (self.right() - new) as u64;
         self.pos.x = new;
@@ -140,23 +563,53 @@ impl Region {
         Ok(self)
     }
 
-    pub fn set_right(&mut self, new: i64) -> Result<&mut Self> {
-        if new < self.left() + MIN_REGION_SIZE.w as i64 {
-            return Err(ErrorKind::InvalidRegion);
-        }
+    pub fn set_right(&mut self, new: i64, min: Rectangle) -> Result<&mut Self> {
+        self.fit_width(new - self.left(), min)?;
 
         self.size.w = (new - self.left()) as u64;
 
         Ok(self)
     }
+
+    fn set_edge(&mut self, direction: Direction, new: i64, min: Rectangle) -> Result<&mut Self> {
+        match direction {
+            Direction::Up => self.set_top(new, min),
+            Direction::Down => self.set_bottom(new, min),
+            Direction::Left => self.set_left(new, min),
+            Direction::Right => self.set_right(new, min),
+        }
+    }
+
+    /// A copy of the region with its top edge moved to `new`, leaving the region itself unchanged.
+    /// As with `try_new`, the copy must be at least the minimum region size.
+    pub fn with_top(mut self, new: i64) -> Result<Region> {
+        Ok(*self.set_top(new, MIN_REGION_SIZE)?)
+    }
+
+    pub fn with_bottom(mut self, new: i64) -> Result<Region> {
+        Ok(*self.set_bottom(new, MIN_REGION_SIZE)?)
+    }
+
+    pub fn with_left(mut self, new: i64) -> Result<Region> {
+        Ok(*self.set_left(new, MIN_REGION_SIZE)?)
+    }
+
+    pub fn with_right(mut self, new: i64) -> Result<Region> {
+        Ok(*self.set_right(new, MIN_REGION_SIZE)?)
+    }
+}
+
+impl Gaps {
+    pub fn new(inner: u64, outer: u64) -> Self {
+        Self { inner, outer }
+    }
 }
 
 impl Workspace {
+    /// Create a workspace with a single tiled region covering all of it, without checking its size. Use `try_new` for
+    /// sizes that aren't known to be at least the minimum region size.
     pub fn new(size: Rectangle) -> Self {
-        let mut this = Self {
-            size,
-            regions: Vec::new(),
-        };
+        let mut this = Self::empty(size);
 
         this.regions
             .extend([Region::new(size, Position::new(0, 0), false)]);
@@ -164,229 +617,5242 @@ impl Workspace {
         this
     }
 
-    /// Create a new region using half the width or height of a sibling region.
-    /// The `direction` specifies which edge of the sibling will be moved to make space for the new region.
-    /// The sibling will be the larger region in the event that the halved dimension of the sibling region is an odd number.
-    pub fn create_region(&mut self, sibling: &mut Region, direction: Direction) -> usize {
-        let mut region = *sibling;
-
-        match direction {
-            Direction::Up => {
-                region.size.h /= 2;
-                sibling.size.h = sibling.size.h / 2 + sibling.size.h % 2;
-                sibling.pos.y += region.size.h as i64;
-            }
-            Direction::Down => {
-                region.size.h /= 2;
-                sibling.size.h = sibling.size.h / 2 + sibling.size.h % 2;
-                region.pos.y += sibling.size.h as i64;
-            }
-            Direction::Left => {
-                region.size.w /= 2;
-                sibling.size.w = sibling.size.w / 2 + sibling.size.w % 2;
-                sibling.pos.x += region.size.w as i64;
-            }
-            Direction::Right => {
-                region.size.w /= 2;
-                sibling.size.w = sibling.size.w / 2 + sibling.size.w % 2;
-                region.pos.x += sibling.size.w as i64;
-            }
+    pub fn try_new(size: Rectangle) -> Result<Self> {
+        if size.w < MIN_REGION_SIZE.w || size.h < MIN_REGION_SIZE.h {
+            return Err(ErrorKind::InvalidRegion);
         }
 
-        self.regions.extend([region]);
-
-        self.regions.len() - 1
+        Ok(Self::new(size))
     }
 
-    pub fn shared_edge_regions(&self, region: &Region, direction: Direction) -> Vec<usize> {
-        self.regions
-            .iter()
-            .enumerate()
-            .filter_map(|(index, sibling)| {
-                if match direction {
-                    Direction::Up => region.top() - sibling.bottom() == 1,
-                    Direction::Down => region.bottom() - sibling.top() == 1,
-                    Direction::Left => region.left() - sibling.right() == 1,
-                    Direction::Right => region.right() - sibling.left() == 1,
-                } {
-                    Some(index)
-                } else {
-                    None
-                }
-            })
-            .collect()
+    /// Create a workspace without any regions, such as to populate `regions` programmatically. Until populated, it
+    /// passes `validate`, which only checks the regions present, so use `assert_tiles_exactly` to reject it. Both
+    /// `layout_issues` and `uncovered_areas` report the whole workspace as uncovered.
+    pub fn empty(size: Rectangle) -> Self {
+        Self {
+            id: WorkspaceId(0),
+            monitor: None,
+            size,
+            regions: Vec::new(),
+            min_region_size: MIN_REGION_SIZE,
+            gaps: Gaps::default(),
+            float_clamp: FloatClamp::Inside,
+            round_bias: RoundBias::Sibling,
+            focused_region: None,
+            struts: (0, 0, 0, 0),
+            history_depth: 0,
+            undo_history: Vec::new(),
+            redo_history: Vec::new(),
+            maximized: None,
+        }
     }
 
-    pub fn adjacent_regions(&self, region: &Region, direction: Direction) -> Vec<usize> {
-        self.shared_edge_regions(region, direction)
-            .into_iter()
-            .filter(|index| {
-                let sibling = self.regions.get(*index).unwrap();
-
-                match direction {
-                    Direction::Up | Direction::Down => {
-                        (sibling.left() >= region.left() && sibling.left() <= region.right())
-                            || (sibling.right() <= region.right()
-                                && sibling.right() >= region.left())
-                    }
-                    Direction::Left | Direction::Right => {
-                        (sibling.top() >= region.top() && sibling.top() <= region.bottom())
-                            || (sibling.bottom() <= region.bottom()
-                                && sibling.bottom() >= region.top())
-                    }
-                }
-            })
-            .collect()
+    /// Reset the layout to a single tiled region covering the whole workspace, as created by `new`. Settings such as
+    /// `gaps` are kept, while focus and any geometry kept by `maximize_region` are cleared.
+    pub fn clear(&mut self) {
+        self.record(|workspace| {
+            workspace.regions = vec![workspace.bounds()];
+            workspace.focused_region = None;
+            workspace.maximized = None;
+        })
     }
 
-    /// Find the adjacent region with the largest overlap on the edge corresponding with `direction`.
-    /// In the event that there are no regions touching the edge of the subject, `None` will be returned.
-    /// This currently does not take into account that several sibling regions may have the same overlap;
-    /// which sibling will be returned is undefined, however it will most likely be the sibling that was
-    /// created the latest as it is going to have a higher index in the internal iterable.
-    pub fn major_adjacent_region(&self, region: &Region, direction: Direction) -> Option<usize> {
-        self.adjacent_regions(region, direction)
-            .into_iter()
-            .map(|index| {
-                let sibling = self.regions.get(index).unwrap();
-
-                (
-                    index,
-                    match direction {
-                        Direction::Up | Direction::Down => i64::abs(
-                            i64::max(sibling.left(), region.left())
-                                - i64::min(sibling.right(), region.right()),
-                        ) as u64,
-                        Direction::Left | Direction::Right => i64::abs(
-                            i64::max(sibling.top(), region.top())
-                                - i64::min(sibling.bottom(), region.bottom()),
-                        ) as u64,
-                    },
-                )
-            })
-            .max_by_key(|x| x.1)
-            .map(|x| x.0)
+    /// Create a new region using half the width or height of a sibling region, returning the index of the new region.
+    /// The `direction` specifies which edge of the sibling will be moved to make space for the new region. In the event
+    /// that the halved dimension of the sibling region is an odd number, `round_bias` decides which region will be the
+    /// larger one. Nothing is changed if halving the sibling would leave either region below the minimum size.
+    pub fn create_region(&mut self, sibling: usize, direction: &Direction) -> Result<usize> {
+        self.split_region(sibling, direction, 0.5)
     }
 
-    pub fn resize_region(&mut self, region: &mut Region, resize: Resize) -> Result<()> {
-        match resize {
-            Resize::Top(top) => {
-                region.set_top(region.top() + top)?;
+    /// Insert a new region on the `direction` side of the region at `relative_to` as with `create_region`, moving
+    /// focus to the new region. Inserting relative to `focused_region` thus keeps focus on the latest region.
+    pub fn insert_region(&mut self, relative_to: usize, direction: &Direction) -> Result<usize> {
+        let index = self.create_region(relative_to, direction)?;
 
-                for index in self.adjacent_regions(region, Direction::Up) {
-                    let sibling = self.regions.get_mut(index).unwrap();
+        self.focused_region = Some(index);
 
-                    sibling.set_bottom(sibling.bottom() - top)?;
-                }
-            }
-            Resize::Bottom(bottom) => {
-                region.set_bottom(region.bottom() + bottom)?;
+        Ok(index)
+    }
 
-                for index in self.adjacent_regions(region, Direction::Down) {
-                    let sibling = self.regions.get_mut(index).unwrap();
+    /// Create a new region taking `ratio` of the width or height of a sibling region, returning the index of the new
+    /// region. The new region's dimension is rounded to give any remainder to the region chosen by `round_bias`.
+    /// `ratio` must lie strictly between 0 and 1, and nothing is changed if the split would leave either region below
+    /// the minimum size.
+    pub fn split_region(
+        &mut self,
+        sibling: usize,
+        direction: &Direction,
+        ratio: f64,
+    ) -> Result<usize> {
+        self.record(|workspace| {
+            let (min, bias) = (workspace.min_region_size, workspace.round_bias);
+            let (split, region) = workspace
+                .region(sibling)?
+                .split_with(direction, ratio, min, bias)?;
 
-                    sibling.set_top(sibling.top() - bottom)?;
-                }
-            }
-            Resize::Left(left) => {
-                region.set_left(region.left() + left)?;
+            *workspace.regions.get_mut(sibling).unwrap() = split;
+            workspace.regions.extend([region]);
 
-                for index in self.adjacent_regions(region, Direction::Left) {
-                    let sibling = self.regions.get_mut(index).unwrap();
+            Ok(workspace.regions.len() - 1)
+        })
+    }
 
-                    sibling.set_right(sibling.right() - left)?;
-                }
+    /// Tile the floating region at `index` by splitting the tiled region at `target` as with `create_region`, with the
+    /// floating region taking the new space on the `direction` side of it. `InvalidRegion` is returned if the region at
+    /// `index` isn't floating or the one at `target` is, and nothing is changed if the split would leave either region
+    /// below the minimum size. The region leaves any tab group it was in, such as one kept from
+    /// `Manager::merge_workspaces`.
+    pub fn tile_float(&mut self, index: usize, target: usize, direction: &Direction) -> Result<()> {
+        self.record(|workspace| {
+            if !workspace.region(index)?.float || workspace.region(target)?.float {
+                return Err(ErrorKind::InvalidRegion);
             }
-            Resize::Right(right) => {
-                region.set_right(region.right() + right)?;
 
-                for index in self.adjacent_regions(region, Direction::Right) {
-                    let sibling = self.regions.get_mut(index).unwrap();
+            let target = workspace.tab_leader(target).unwrap_or(target);
+            let (min, bias) = (workspace.min_region_size, workspace.round_bias);
+            let (split, tiled) = workspace.regions[target].split_with(direction, 0.5, min, bias)?;
+            let region = workspace.regions.get_mut(index).unwrap();
 
-                    sibling.set_left(sibling.left() - right)?;
-                }
-            }
-            Resize::TopLeft(top, left) => {
-                self.resize_region(region, Resize::Top(top))?;
-                self.resize_region(region, Resize::Left(left))?;
-            }
-            Resize::TopRight(top, right) => {
-                self.resize_region(region, Resize::Top(top))?;
-                self.resize_region(region, Resize::Right(right))?;
-            }
-            Resize::BottomLeft(bottom, left) => {
-                self.resize_region(region, Resize::Bottom(bottom))?;
-                self.resize_region(region, Resize::Left(left))?;
-            }
-            Resize::BottomRight(bottom, right) => {
-                self.resize_region(region, Resize::Bottom(bottom))?;
-                self.resize_region(region, Resize::Right(right))?;
-            }
-        }
+            region.size = tiled.size;
+            region.pos = tiled.pos;
+            region.float = false;
+            region.tab_group = None;
+            *workspace.regions.get_mut(target).unwrap() = split;
 
-        Ok(())
+            Ok(())
+        })
     }
 
-    pub fn swap_region(&mut self, region: &mut Region, direction: Direction) -> Result<()> {
-        let index = self
-            .major_adjacent_region(region, direction)
-            .ok_or(ErrorKind::NoAdjacentRegions)?;
-        let sibling = self.regions.get_mut(index).unwrap();
+    /// Replace the tiled regions with a binary space partition of `count` regions, returning their indices.
+    /// Starting from a single region covering the workspace, the largest region is repeatedly split in half,
+    /// alternating between vertical and horizontal splits. Floating regions are kept, but focus is cleared.
+    /// Nothing is changed if `count` is zero or the partition would need regions below the minimum size.
+    pub fn bsp_layout(&mut self, count: usize) -> Result<Vec<usize>> {
+        self.record(|workspace| {
+            if count == 0 {
+                return Err(ErrorKind::InvalidRegion);
+            }
 
-        std::mem::swap(&mut region.size, &mut sibling.size);
-        std::mem::swap(&mut region.pos, &mut sibling.pos);
+            let regions = workspace.regions.clone();
 
-        Ok(())
-    }
+            workspace.regions.retain(|region| region.float);
+            workspace.regions.extend([workspace.bounds()]);
 
-    // pub fn resize(&mut self, new: Rectangle) -> Result<()> {
-    //     let scale_w = new.w as f64 / self.size.w as f64;
-    //     let scale_h = new.h as f64 / self.size.h as f64;
+            let mut indices = vec![workspace.regions.len() - 1];
 
-    //     for mut region in &mut self.regions {
-    //         region.pos.x = (region.pos.x as f64 * scale_w) as i64;
-    //         region.pos.y = (region.pos.y as f64 * scale_h) as i64;
-    //         region.size.w = (region.size.w as f64 * scale_w) as u64;
-    //         region.size.h = (region.size.h as f64 * scale_h) as u64;
-    //     }
+            for split in 1..count {
+                let largest = workspace.largest_region().unwrap();
+                let direction = if split % 2 == 1 {
+                    Direction::Right
+                } else {
+                    Direction::Down
+                };
 
-    //     self.size = new;
+                match workspace.create_region(largest, &direction) {
+                    Ok(index) => indices.extend([index]),
+                    Err(error) => {
+                        workspace.regions = regions;
 
-    //     Ok(())
-    // }
-}
+                        return Err(error);
+                    }
+                }
+            }
+
+            workspace.focused_region = None;
+
+            Ok(indices)
+        })
+    }
+
+    /// Replace the tiled regions with a master region on the left taking `master_ratio` of the width, and the remaining
+    /// `count - 1` regions stacked with equal heights on the right, returning their indices with the master first. The
+    /// master width is clamped so that both columns stay at least the minimum width. Floating regions are kept, but
+    /// focus is cleared. Nothing is changed if `count` is zero or the stack would need regions below the minimum
+    /// height.
+    pub fn master_stack(&mut self, count: usize, master_ratio: f64) -> Result<Vec<usize>> {
+        self.record(|workspace| {
+            if count == 0 {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            let mut regions = workspace
+                .regions
+                .iter()
+                .copied()
+                .filter(|region| region.float)
+                .collect::<Vec<_>>();
+            let first = regions.len();
+            let bounds = workspace.bounds();
+            let size = bounds.size;
+
+            if count == 1 {
+                regions.extend([bounds]);
+            } else {
+                let stack = (count - 1) as u64;
+                let (height, remainder) = (size.h / stack, size.h % stack);
+
+                if size.w < workspace.min_region_size.w * 2 || height < workspace.min_region_size.h
+                {
+                    return Err(ErrorKind::InvalidRegion);
+                }
+
+                let master = ((size.w as f64 * master_ratio) as u64).clamp(
+                    workspace.min_region_size.w,
+                    size.w - workspace.min_region_size.w,
+                );
+                let mut y = 0;
+
+                regions.extend([Region::new(
+                    Rectangle::new(master, size.h),
+                    bounds.pos,
+                    false,
+                )]);
+
+                for index in 0..stack {
+                    let h = height + u64::from(index < remainder);
+
+                    regions.extend([Region::new(
+                        Rectangle::new(size.w - master, h),
+                        bounds.pos.offset(master as i64, y),
+                        false,
+                    )]);
+                    y += h as i64;
+                }
+            }
+
+            workspace.regions = regions;
+            workspace.focused_region = None;
+
+            Ok((first..workspace.regions.len()).collect())
+        })
+    }
+
+    /// Replace the tiled regions with an evenly spaced grid of `rows` by `cols` regions, returning their indices in
+    /// row-major order. Remainder pixels go to the earliest rows and columns, one each. Floating regions are kept,
+    /// but focus is cleared. Nothing is changed if the grid is empty or would need regions below the minimum size.
+    pub fn grid_layout(&mut self, rows: usize, cols: usize) -> Result<Vec<usize>> {
+        self.record(|workspace| {
+            if rows == 0 || cols == 0 {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            let (rows, cols) = (rows as u64, cols as u64);
+            let bounds = workspace.bounds();
+            let (width, width_remainder) = (bounds.size.w / cols, bounds.size.w % cols);
+            let (height, height_remainder) = (bounds.size.h / rows, bounds.size.h % rows);
+
+            if width < workspace.min_region_size.w || height < workspace.min_region_size.h {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            let mut regions = workspace
+                .regions
+                .iter()
+                .copied()
+                .filter(|region| region.float)
+                .collect::<Vec<_>>();
+            let first = regions.len();
+            let mut y = 0;
+
+            for row in 0..rows {
+                let h = height + u64::from(row < height_remainder);
+                let mut x = 0;
+
+                for col in 0..cols {
+                    let w = width + u64::from(col < width_remainder);
+
+                    regions.extend([Region::new(
+                        Rectangle::new(w, h),
+                        bounds.pos.offset(x, y),
+                        false,
+                    )]);
+                    x += w as i64;
+                }
+
+                y += h as i64;
+            }
+
+            workspace.regions = regions;
+            workspace.focused_region = None;
+
+            Ok((first..workspace.regions.len()).collect())
+        })
+    }
+
+    /// Compute the on-screen geometry of every region with gaps applied, in the same order as `regions`.
+    /// Tiled edges against the workspace bounds are inset by the outer gap, while shared edges are inset by half
+    /// of the inner gap on either side. Floating regions are left untouched.
+    pub fn layout_rects(&self) -> Vec<Region> {
+        let bounds = self.bounds();
+        let inset = |edge: i64, bound: i64, inner: u64| {
+            if edge == bound {
+                self.gaps.outer as i64
+            } else {
+                inner as i64
+            }
+        };
+
+        self.regions
+            .iter()
+            .map(|region| {
+                if region.float {
+                    return *region;
+                }
+
+                let left = region.left() + inset(region.left(), bounds.left(), self.gaps.inner / 2);
+                let top = region.top() + inset(region.top(), bounds.top(), self.gaps.inner / 2);
+                let right = region.right()
+                    - inset(
+                        region.right(),
+                        bounds.right(),
+                        self.gaps.inner - self.gaps.inner / 2,
+                    );
+                let bottom = region.bottom()
+                    - inset(
+                        region.bottom(),
+                        bounds.bottom(),
+                        self.gaps.inner - self.gaps.inner / 2,
+                    );
+
+                Region::new(
+                    Rectangle::new(
+                        i64::max(right - left, 0) as u64,
+                        i64::max(bottom - top, 0) as u64,
+                    ),
+                    Position::new(left, top),
+                    false,
+                )
+            })
+            .collect()
+    }
+
+    /// The number of regions, both tiled and floating.
+    pub fn region_count(&self) -> usize {
+        self.regions.len()
+    }
+
+    /// The number of floating regions.
+    pub fn count_floating(&self) -> usize {
+        self.regions.iter().filter(|region| region.float).count()
+    }
+
+    /// The number of tiled regions, counting every region in a tab group rather than just the one taking part in
+    /// tiling, so that this and `count_floating` add up to `region_count`.
+    pub fn count_tiled(&self) -> usize {
+        self.regions.iter().filter(|region| !region.float).count()
+    }
+
+    /// Whether the workspace has no tiled regions. Floating regions don't count, so a workspace holding only
+    /// floating regions is empty.
+    pub fn is_empty(&self) -> bool {
+        self.tiled_regions().next().is_none()
+    }
+
+    pub fn region(&self, index: usize) -> Result<&Region> {
+        self.regions.get(index).ok_or(ErrorKind::UnknownRegion)
+    }
+
+    pub fn region_mut(&mut self, index: usize) -> Result<&mut Region> {
+        self.regions.get_mut(index).ok_or(ErrorKind::UnknownRegion)
+    }
+
+    /// The geometry of the region at `index` as fractions of the workspace size, in the order `(x, y, w, h)`.
+    pub fn region_normalized(&self, index: usize) -> Result<(f64, f64, f64, f64)> {
+        let region = self.region(index)?;
+        let (w, h) = (self.size.w as f64, self.size.h as f64);
+
+        Ok((
+            region.pos.x as f64 / w,
+            region.pos.y as f64 / h,
+            region.size.w as f64 / w,
+            region.size.h as f64 / h,
+        ))
+    }
+
+    /// Set the geometry of the region at `index` from fractions of the workspace size, as returned by
+    /// `region_normalized`. Edges rather than sizes are rounded to the nearest pixel, so regions sharing an edge
+    /// as fractions still share it. Nothing is changed if the region would be below the minimum size.
+    pub fn set_region_normalized(
+        &mut self,
+        index: usize,
+        (x, y, w, h): (f64, f64, f64, f64),
+    ) -> Result<()> {
+        self.record(|workspace| {
+            let (width, height) = (workspace.size.w as f64, workspace.size.h as f64);
+            let min = workspace.min_region_size;
+            let (left, right) = ((x * width).round() as i64, ((x + w) * width).round() as i64);
+            let (top, bottom) = (
+                (y * height).round() as i64,
+                ((y + h) * height).round() as i64,
+            );
+            let region = workspace.region_mut(index)?;
+
+            if right - left < min.w as i64 || bottom - top < min.h as i64 {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            region.pos = Position::new(left, top);
+            region.size = Rectangle::new((right - left) as u64, (bottom - top) as u64);
+
+            Ok(())
+        })
+    }
+
+    /// The region at `index` with its position expressed for a Y axis growing in the direction of `axis`.
+    pub fn region_in_convention(&self, index: usize, axis: YAxis) -> Result<Region> {
+        Ok(self.region_from_convention(*self.region(index)?, axis))
+    }
+
+    /// Convert `region` from having its position expressed for a Y axis growing in the direction of `axis` back to
+    /// the workspace's own convention. Flipping the Y axis is its own inverse, so this also converts the other way.
+    pub fn region_from_convention(&self, mut region: Region, axis: YAxis) -> Region {
+        if axis == YAxis::Up {
+            region.pos.y = self.size.h as i64 - region.bottom();
+        }
+
+        region
+    }
+
+    /// Iterate over the regions taking part in tiling, along with their indices in `regions`.
+    /// Of the tiled regions in a tab group, only the first takes part, with the rest following its geometry.
+    pub fn tiled_regions(&self) -> impl Iterator<Item = (usize, &Region)> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(index, region)| !region.float && self.tab_leader(*index).is_none())
+    }
+
+    /// The index of the first tiled region in the tab group of the tiled region at `index`, if there is an earlier
+    /// one for it to follow.
+    fn tab_leader(&self, index: usize) -> Option<usize> {
+        let region = self.regions.get(index)?;
+        let group = region.tab_group.filter(|_| !region.float)?;
+
+        self.regions
+            .iter()
+            .take(index)
+            .position(|leader| !leader.float && leader.tab_group == Some(group))
+    }
+
+    /// Move every region following a tab group leader to the leader's geometry.
+    fn sync_tab_groups(&mut self) {
+        for index in 0..self.regions.len() {
+            if let Some(leader) = self.tab_leader(index) {
+                let (size, pos) = {
+                    let leader = self.regions.get(leader).unwrap();
+
+                    (leader.size, leader.pos)
+                };
+                let region = self.regions.get_mut(index).unwrap();
+
+                region.size = size;
+                region.pos = pos;
+            }
+        }
+    }
+
+    /// Group the tiled regions at `indices` into a new tab group, returning its id. Every region takes on the
+    /// geometry of the first of them, with the space each of the others leaves behind reclaimed by its neighbors as
+    /// with `remove_region`. Resizing any of them resizes the whole group. `InvalidRegion` is returned if `indices`
+    /// is empty or includes a floating region or one already in a tab group.
+    pub fn group_as_tabs(&mut self, indices: &[usize]) -> Result<u32> {
+        self.record(|workspace| {
+            for index in indices {
+                let region = workspace.region(*index)?;
+
+                if region.float || region.tab_group.is_some() {
+                    return Err(ErrorKind::InvalidRegion);
+                }
+            }
+
+            let mut indices = indices.to_vec();
+
+            indices.sort_unstable();
+            indices.dedup();
+
+            let leader = *indices.first().ok_or(ErrorKind::InvalidRegion)?;
+            let group = workspace
+                .regions
+                .iter()
+                .filter_map(|region| region.tab_group)
+                .max()
+                .map_or(0, |group| group + 1);
+            let regions = workspace.regions.clone();
+            let result = workspace.join_tab_group(&indices, leader, group);
+
+            if result.is_err() {
+                workspace.regions = regions;
+            }
+
+            result.map(|()| group)
+        })
+    }
+
+    fn join_tab_group(&mut self, indices: &[usize], leader: usize, group: u32) -> Result<()> {
+        self.regions.get_mut(leader).unwrap().tab_group = Some(group);
+
+        for index in indices.iter().copied().filter(|index| *index != leader) {
+            let region = *self.regions.get(index).unwrap();
+            let leader = *self.regions.get(leader).unwrap();
+
+            self.regions.get_mut(index).unwrap().tab_group = Some(group);
+
+            if (region.size, region.pos) != (leader.size, leader.pos) {
+                self.backfill(&region)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Iterate over the floating regions, along with their indices in `regions`.
+    pub fn floating_regions(&self) -> impl Iterator<Item = (usize, &Region)> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, region)| region.float)
+    }
+
+    /// Find the index of the first region matching `pred`.
+    pub fn find_region(&self, pred: impl Fn(&Region) -> bool) -> Option<usize> {
+        self.regions.iter().position(pred)
+    }
+
+    /// Find the indices of every region matching `pred`, in order.
+    pub fn find_regions(&self, pred: impl Fn(&Region) -> bool) -> Vec<usize> {
+        self.regions
+            .iter()
+            .enumerate()
+            .filter(|(_, region)| pred(region))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// The indices of every region in reading order: top to bottom, then left to right. Regions at the same
+    /// position stay in index order.
+    pub fn regions_sorted(&self) -> Vec<usize> {
+        let mut indices = (0..self.regions.len()).collect::<Vec<_>>();
+
+        indices.sort_by_key(|index| {
+            let region = self.regions.get(*index).unwrap();
+
+            (region.top(), region.left())
+        });
+        indices
+    }
+
+    /// Find the tiled region with the largest area. When several are equally large, the lowest index is returned.
+    pub fn largest_region(&self) -> Option<usize> {
+        self.tiled_regions()
+            .min_by_key(|(_, region)| std::cmp::Reverse(region.area()))
+            .map(|(index, _)| index)
+    }
+
+    /// Find the tiled region with the smallest area. When several are equally small, the lowest index is returned.
+    pub fn smallest_region(&self) -> Option<usize> {
+        self.tiled_regions()
+            .min_by_key(|(_, region)| region.area())
+            .map(|(index, _)| index)
+    }
+
+    /// Find the region containing `pos`. Floating regions are drawn above tiled ones and so take precedence,
+    /// with the topmost floating region by `z` taking precedence, or the later one when they are level.
+    pub fn region_at(&self, pos: Position) -> Option<usize> {
+        let mut regions = self
+            .regions
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, region)| region.contains(pos));
+
+        regions
+            .clone()
+            .filter(|(_, region)| region.float)
+            .min_by_key(|(_, region)| std::cmp::Reverse(region.z))
+            .or_else(|| regions.next())
+            .map(|(index, _)| index)
+    }
+
+    /// Find the region edge closest to `pos`, for starting a drag-resize, as the region and the direction its edge
+    /// faces. Only edges within `threshold` of `pos` and running alongside it count, and near a corner the closer of
+    /// the two edges is chosen. When edges are equally close, such as either side of a shared edge, the region found
+    /// by `region_at` is preferred, followed by the lowest index.
+    pub fn nearest_edge(&self, pos: Position, threshold: u64) -> Option<(usize, Direction)> {
+        let under = self.region_at(pos);
+
+        self.regions
+            .iter()
+            .enumerate()
+            .flat_map(|(index, region)| {
+                Direction::all().into_iter().filter_map(move |direction| {
+                    let (along, across) = match direction.axis() {
+                        Axis::Horizontal => (pos.y, pos.x),
+                        Axis::Vertical => (pos.x, pos.y),
+                    };
+                    let (start, end) = region.extent(direction.axis().perpendicular());
+                    let distance = region.edge(direction).abs_diff(across);
+
+                    if along < start || along > end || distance > threshold {
+                        return None;
+                    }
+
+                    Some((distance, under != Some(index), index, direction))
+                })
+            })
+            .min_by_key(|(distance, other, _, _)| (*distance, *other))
+            .map(|(_, _, index, direction)| (index, direction))
+    }
+
+    /// Raise the floating region at `index` above every other floating region. Tiled regions aren't stacked;
+    /// `InvalidRegion` is returned for them.
+    pub fn raise_region(&mut self, index: usize) -> Result<()> {
+        self.restack(index, true)
+    }
+
+    /// Lower the floating region at `index` below every other floating region. Tiled regions aren't stacked;
+    /// `InvalidRegion` is returned for them.
+    pub fn lower_region(&mut self, index: usize) -> Result<()> {
+        self.restack(index, false)
+    }
+
+    /// Move the floating region at `index` to the top or bottom of the stacking order, renumbering `z` for every
+    /// floating region from zero upwards while keeping the order of the rest.
+    fn restack(&mut self, index: usize, top: bool) -> Result<()> {
+        self.record(|workspace| {
+            if !workspace.region(index)?.float {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            let mut floats = workspace
+                .floating_regions()
+                .filter(|(other, _)| *other != index)
+                .map(|(other, region)| (other, region.z))
+                .collect::<Vec<_>>();
+
+            floats.sort_by_key(|(other, z)| (*z, *other));
+
+            if top {
+                floats.extend([(index, 0)]);
+            } else {
+                floats.insert(0, (index, 0));
+            }
+
+            for (z, (other, _)) in floats.into_iter().enumerate() {
+                workspace.regions.get_mut(other).unwrap().z = z as u32;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Find the region whose center is nearest to `pos`, such as when `pos` falls in a gap between regions.
+    /// When several regions are equally near, the one with the lowest index is returned.
+    pub fn closest_region_to(&self, pos: Position) -> Option<usize> {
+        self.regions
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, region)| {
+                let center = region.center();
+                let (dx, dy) = (
+                    (center.x as i128 - pos.x as i128).unsigned_abs(),
+                    (center.y as i128 - pos.y as i128).unsigned_abs(),
+                );
+
+                (dx * dx).saturating_add(dy * dy)
+            })
+            .map(|(index, _)| index)
+    }
+
+    /// The area of the workspace left for tiled regions once the `struts` are reserved, as a tiled region.
+    pub fn bounds(&self) -> Region {
+        Self::bounds_within(self.size, self.struts)
+    }
+
+    fn bounds_within(size: Rectangle, (top, bottom, left, right): (u64, u64, u64, u64)) -> Region {
+        Region::new(
+            Rectangle::new(
+                size.w.saturating_sub(left + right),
+                size.h.saturating_sub(top + bottom),
+            ),
+            Position::new(left as i64, top as i64),
+            false,
+        )
+    }
+
+    /// Whether `region` lies entirely inside the workspace `bounds`.
+    pub fn is_within(&self, region: &Region) -> bool {
+        let bounds = self.bounds();
+
+        region.left() >= bounds.left()
+            && region.top() >= bounds.top()
+            && region.right() <= bounds.right()
+            && region.bottom() <= bounds.bottom()
+    }
+
+    /// Describe every region for exporting the layout, in index order.
+    pub fn describe(&self) -> Vec<RegionInfo> {
+        self.regions
+            .iter()
+            .enumerate()
+            .map(|(index, region)| RegionInfo {
+                index,
+                rect: region.size,
+                pos: region.pos,
+                float: region.float,
+            })
+            .collect()
+    }
+
+    /// Replace the regions with those in `infos`, such as a layout saved from `describe`, ordered by their indices.
+    /// Nothing is changed and `InvalidRegion` is returned unless the indices run from zero without any repeated or
+    /// missing, and the new layout passes both `validate` and `assert_tiles_exactly`.
+    pub fn apply_layout(&mut self, infos: &[RegionInfo]) -> Result<()> {
+        self.record(|workspace| {
+            let mut infos = infos.to_vec();
+
+            infos.sort_by_key(|info| info.index);
+
+            if infos
+                .iter()
+                .enumerate()
+                .any(|(index, info)| info.index != index)
+            {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            let regions = infos
+                .into_iter()
+                .map(|info| Region::new(info.rect, info.pos, info.float))
+                .collect();
+            let previous = std::mem::replace(&mut workspace.regions, regions);
+
+            if let Err(error) = workspace
+                .validate()
+                .and_then(|()| workspace.assert_tiles_exactly())
+            {
+                workspace.regions = previous;
+
+                return Err(error);
+            }
+
+            workspace.maximized = None;
+            workspace.restore_focus();
+
+            Ok(())
+        })
+    }
+
+    /// Check that every tiled region is at least the minimum size, lies within the workspace bounds,
+    /// and does not overlap any other tiled region. Gaps aren't checked, so a workspace without any tiled regions
+    /// passes; use `assert_tiles_exactly` to also require the workspace to be covered.
+    pub fn validate(&self) -> Result<()> {
+        for (index, region) in self.tiled_regions() {
+            if region.size.w < self.min_region_size.w
+                || region.size.h < self.min_region_size.h
+                || !self.is_within(region)
+                || self
+                    .tiled_regions()
+                    .any(|(other, sibling)| other != index && region.intersects(sibling))
+            {
+                return Err(ErrorKind::InvalidRegion);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Check that the tiled regions cover the workspace exactly, lying within its bounds without overlapping each other
+    /// and with their areas adding up to its own, returning `InvalidRegion` otherwise. Unlike `validate`, this also
+    /// rejects layouts leaving gaps, which makes it suitable for asserting on layouts in tests.
+    pub fn assert_tiles_exactly(&self) -> Result<()> {
+        let mut area = 0u64;
+
+        for (index, region) in self.tiled_regions() {
+            if !self.is_within(region)
+                || self
+                    .tiled_regions()
+                    .any(|(other, sibling)| other > index && region.intersects(sibling))
+            {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            area = area.saturating_add(region.area());
+        }
+
+        if area != self.bounds().area() {
+            return Err(ErrorKind::InvalidRegion);
+        }
+
+        Ok(())
+    }
+
+    /// List every problem with the tiled layout: regions past the workspace bounds, pairs of overlapping regions,
+    /// and areas of the workspace not covered by any region.
+    pub fn layout_issues(&self) -> Vec<LayoutIssue> {
+        let mut issues = self
+            .tiled_regions()
+            .filter(|(_, region)| !self.is_within(region))
+            .map(|(index, _)| LayoutIssue::OutOfBounds(index))
+            .collect::<Vec<_>>();
+
+        for (index, region) in self.tiled_regions() {
+            issues.extend(
+                self.tiled_regions()
+                    .filter(|(other, sibling)| *other > index && region.intersects(sibling))
+                    .map(|(other, _)| LayoutIssue::Overlap(index, other)),
+            );
+        }
+
+        issues.extend(
+            self.uncovered_areas()
+                .into_iter()
+                .map(|gap| LayoutIssue::Gap(gap.size, gap.pos)),
+        );
+
+        issues
+    }
+
+    /// Decompose the parts of the workspace `bounds` not covered by tiled regions into rectangles, ordered top to
+    /// bottom and then left to right. The bounds are cut into cells along every region edge, and the uncovered cells
+    /// are merged into runs along each row before runs spanning the same columns are merged down the rows.
+    #[doc(alias = "gaps")]
+    pub fn uncovered_areas(&self) -> Vec<Region> {
+        let bounds = self.bounds();
+        let (left, right, top, bottom) =
+            (bounds.left(), bounds.right(), bounds.top(), bounds.bottom());
+        let (mut xs, mut ys) = (vec![left, right], vec![top, bottom]);
+
+        for (_, region) in self.tiled_regions() {
+            xs.extend([
+                region.left().clamp(left, right),
+                region.right().clamp(left, right),
+            ]);
+            ys.extend([
+                region.top().clamp(top, bottom),
+                region.bottom().clamp(top, bottom),
+            ]);
+        }
+
+        for lines in [&mut xs, &mut ys] {
+            lines.sort_unstable();
+            lines.dedup();
+        }
+
+        let mut open: Vec<(i64, i64, i64)> = Vec::new();
+        let mut areas = Vec::new();
+        let mut close = |(left, right, top): (i64, i64, i64), bottom: i64| {
+            areas.extend([Region::new(
+                Rectangle::new((right - left) as u64, (bottom - top) as u64),
+                Position::new(left, top),
+                false,
+            )]);
+        };
+
+        for row in ys.windows(2) {
+            let mut runs: Vec<(i64, i64)> = Vec::new();
+
+            for column in xs.windows(2) {
+                let covered = self.tiled_regions().any(|(_, region)| {
+                    region.left() <= column[0]
+                        && region.right() >= column[1]
+                        && region.top() <= row[0]
+                        && region.bottom() >= row[1]
+                });
+
+                if covered {
+                    continue;
+                }
+
+                match runs.last_mut() {
+                    Some(run) if run.1 == column[0] => run.1 = column[1],
+                    _ => runs.extend([(column[0], column[1])]),
+                }
+            }
+
+            let mut next = Vec::new();
+
+            for (left, right) in runs {
+                match open.iter().position(|x| x.0 == left && x.1 == right) {
+                    Some(index) => next.extend([open.remove(index)]),
+                    None => next.extend([(left, right, row[0])]),
+                }
+            }
+
+            for area in open {
+                close(area, row[0]);
+            }
+
+            open = next;
+        }
+
+        for area in open {
+            close(area, bottom);
+        }
+
+        areas.sort_by_key(|area| (area.top(), area.left()));
+        areas
+    }
+
+    /// Find the tiled regions whose facing edge lies on the same line as the edge of `region` facing `direction`, as
+    /// the edges of regions split from one another do, whether or not they overlap along it. Regions separated by
+    /// even a pixel don't share an edge. Floating regions never share edges as they take no part in tiling.
+    pub fn shared_edge_regions(&self, region: &Region, direction: Direction) -> Vec<usize> {
+        self.tiled_regions()
+            .filter_map(|(index, sibling)| {
+                if region.edge(direction) == sibling.edge(direction.opposite()) {
+                    Some(index)
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Find the tiled regions sharing some length of the edge of `region` facing `direction`: those found by
+    /// `shared_edge_regions` that overlap `region` across that edge. Regions meeting only at a corner aren't adjacent.
+    pub fn adjacent_regions(&self, region: &Region, direction: Direction) -> Vec<usize> {
+        self.shared_edge_regions(region, direction)
+            .into_iter()
+            .filter(|index| region.overlaps_across(self.regions.get(*index).unwrap(), direction))
+            .collect()
+    }
+
+    /// Find the segment of the edge of the region at `a` facing `direction` that it shares with the tiled region at
+    /// `b`, as its start and end points. `None` is returned if the regions don't share any length of that edge.
+    pub fn shared_edge_segment(
+        &self,
+        a: usize,
+        b: usize,
+        direction: &Direction,
+    ) -> Option<(Position, Position)> {
+        let region = self.regions.get(a)?;
+        let sibling = self.regions.get(b)?;
+
+        if !self.shared_edge_regions(region, *direction).contains(&b) {
+            return None;
+        }
+
+        let axis = direction.axis().perpendicular();
+        let ((start, end), (sibling_start, sibling_end)) =
+            (region.extent(axis), sibling.extent(axis));
+        let (start, end) = (i64::max(start, sibling_start), i64::min(end, sibling_end));
+        let edge = region.edge(*direction);
+
+        if start >= end {
+            return None;
+        }
+
+        Some(match axis {
+            Axis::Horizontal => (Position::new(start, edge), Position::new(end, edge)),
+            Axis::Vertical => (Position::new(edge, start), Position::new(edge, end)),
+        })
+    }
+
+    /// Find every tiled region sharing some length of an edge with the region at `index`, along with the direction
+    /// of that edge. Each region is listed once, in the order of `Direction::all`. The result is empty for an
+    /// unknown index.
+    pub fn regions_touching(&self, index: usize) -> Vec<(usize, Direction)> {
+        let Some(region) = self.regions.get(index) else {
+            return Vec::new();
+        };
+        let mut touching = Vec::<(usize, Direction)>::new();
+
+        for direction in Direction::all() {
+            for sibling in self.adjacent_regions(region, direction) {
+                if sibling != index && !touching.iter().any(|(other, _)| *other == sibling) {
+                    touching.extend([(sibling, direction)]);
+                }
+            }
+        }
+
+        touching
+    }
+
+    /// Find every tiled region lying entirely beyond the edge of the region at `from` facing `direction` and
+    /// overlapping it across that edge, ordered from nearest to furthest. The result is empty for an unknown index.
+    pub fn regions_in_direction(&self, from: usize, direction: &Direction) -> Vec<usize> {
+        let region = match self.regions.get(from) {
+            Some(region) => region,
+            None => return Vec::new(),
+        };
+        let distance = |sibling: &Region| match direction {
+            Direction::Up | Direction::Left => {
+                region.edge(*direction) - sibling.edge(direction.opposite())
+            }
+            Direction::Down | Direction::Right => {
+                sibling.edge(direction.opposite()) - region.edge(*direction)
+            }
+        };
+        let mut regions = self
+            .tiled_regions()
+            .filter(|(index, sibling)| {
+                *index != from
+                    && distance(sibling) >= 0
+                    && region.overlaps_across(sibling, *direction)
+            })
+            .map(|(index, sibling)| (index, distance(sibling)))
+            .collect::<Vec<_>>();
+
+        regions.sort_by_key(|x| x.1);
+        regions.into_iter().map(|x| x.0).collect()
+    }
+
+    /// Find the adjacent region with the largest overlap on the edge corresponding with `direction`.
+    /// In the event that there are no regions touching the edge of the subject, `None` will be returned.
+    /// When several sibling regions have the same overlap, the one with the lowest index is returned.
+    pub fn major_adjacent_region(&self, region: &Region, direction: Direction) -> Option<usize> {
+        self.adjacent_regions(region, direction)
+            .into_iter()
+            .map(|index| {
+                let axis = direction.axis().perpendicular();
+                let (start, end) = region.extent(axis);
+                let (sibling_start, sibling_end) = self.regions.get(index).unwrap().extent(axis);
+
+                (
+                    index,
+                    i64::abs(i64::max(sibling_start, start) - i64::min(sibling_end, end)) as u64,
+                )
+            })
+            .min_by_key(|x| std::cmp::Reverse(x.1))
+            .map(|x| x.0)
+    }
+
+    /// Find the region that focus should move to from the region at `from` when moving in `direction`.
+    /// This is the neighbor sharing the longest stretch of the edge facing `direction`.
+    pub fn focus_neighbor(&self, from: usize, direction: &Direction) -> Result<usize> {
+        let region = self.region(from)?;
+
+        self.major_adjacent_region(region, *direction)
+            .ok_or(ErrorKind::NoAdjacentRegions)
+    }
+
+    /// Move one or two edges of `region` by the deltas in `resize`, moving the edges of adjacent regions with it.
+    /// The resize either applies in full or not at all: if any edge would leave a region below the minimum size,
+    /// `InvalidRegion` is returned and neither `region` nor the workspace are changed.
+    ///
+    /// `region` is separate from `regions`, so the resized region must be written back by the caller, and passing a
+    /// stale copy leaves the workspace inconsistent. Prefer `resize_region_by_index`, which avoids both.
+    pub fn resize_region(&mut self, region: &mut Region, resize: Resize) -> Result<()> {
+        self.record(|workspace| {
+            let (regions, subject) = (workspace.regions.clone(), *region);
+            let result = workspace.apply_resize(region, resize);
+
+            if result.is_err() {
+                workspace.regions = regions;
+                *region = subject;
+            }
+
+            result
+        })
+    }
+
+    /// Resize the region at `index` as with `resize_region`, updating it in `regions` directly.
+    /// Resizing a region in a tab group resizes the whole group.
+    pub fn resize_region_by_index(&mut self, index: usize, resize: &Resize) -> Result<()> {
+        self.record(|workspace| {
+            workspace.region(index)?;
+
+            let index = workspace.tab_leader(index).unwrap_or(index);
+            let mut region = *workspace.regions.get(index).unwrap();
+
+            workspace.resize_region(&mut region, *resize)?;
+            *workspace.regions.get_mut(index).unwrap() = region;
+
+            Ok(())
+        })
+    }
+
+    fn apply_resize(&mut self, region: &mut Region, resize: Resize) -> Result<()> {
+        match resize {
+            Resize::Top(top) => {
+                let siblings = self.resize_neighbors(region, Direction::Up);
+
+                region.set_top(region.top() + top, self.min_region_size)?;
+
+                for index in siblings {
+                    let sibling = self.regions.get_mut(index).unwrap();
+
+                    sibling.set_bottom(sibling.bottom() + top, self.min_region_size)?;
+                }
+            }
+            Resize::Bottom(bottom) => {
+                let siblings = self.resize_neighbors(region, Direction::Down);
+
+                region.set_bottom(region.bottom() + bottom, self.min_region_size)?;
+
+                for index in siblings {
+                    let sibling = self.regions.get_mut(index).unwrap();
+
+                    sibling.set_top(sibling.top() + bottom, self.min_region_size)?;
+                }
+            }
+            Resize::Left(left) => {
+                let siblings = self.resize_neighbors(region, Direction::Left);
+
+                region.set_left(region.left() + left, self.min_region_size)?;
+
+                for index in siblings {
+                    let sibling = self.regions.get_mut(index).unwrap();
+
+                    sibling.set_right(sibling.right() + left, self.min_region_size)?;
+                }
+            }
+            Resize::Right(right) => {
+                let siblings = self.resize_neighbors(region, Direction::Right);
+
+                region.set_right(region.right() + right, self.min_region_size)?;
+
+                for index in siblings {
+                    let sibling = self.regions.get_mut(index).unwrap();
+
+                    sibling.set_left(sibling.left() + right, self.min_region_size)?;
+                }
+            }
+            Resize::TopLeft(top, left) => {
+                self.apply_resize(region, Resize::Top(top))?;
+                self.apply_resize(region, Resize::Left(left))?;
+            }
+            Resize::TopRight(top, right) => {
+                self.apply_resize(region, Resize::Top(top))?;
+                self.apply_resize(region, Resize::Right(right))?;
+            }
+            Resize::BottomLeft(bottom, left) => {
+                self.apply_resize(region, Resize::Bottom(bottom))?;
+                self.apply_resize(region, Resize::Left(left))?;
+            }
+            Resize::BottomRight(bottom, right) => {
+                self.apply_resize(region, Resize::Bottom(bottom))?;
+                self.apply_resize(region, Resize::Right(right))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Find the tiled regions whose edges move along with the edge of `region` facing `direction` when resizing it.
+    /// Floating regions take no part in tiling, so resizing one never moves its neighbors.
+    fn resize_neighbors(&self, region: &Region, direction: Direction) -> Vec<usize> {
+        if region.float {
+            return Vec::new();
+        }
+
+        self.adjacent_regions(region, direction)
+    }
+
+    /// Find the regions other than the one at `index` that `resize_region_by_index` would move for `resize`, without
+    /// changing anything. This includes regions in tab groups following the ones moved. The result is empty if the
+    /// resize would fail.
+    pub fn resize_affects(&self, index: usize, resize: &Resize) -> Vec<usize> {
+        let mut workspace = Workspace {
+            regions: self.regions.clone(),
+            history_depth: 0,
+            undo_history: Vec::new(),
+            redo_history: Vec::new(),
+            maximized: None,
+            ..*self
+        };
+
+        if workspace.resize_region_by_index(index, resize).is_err() {
+            return Vec::new();
+        }
+
+        let group = self.regions.get(index).unwrap().tab_group;
+
+        workspace
+            .regions
+            .iter()
+            .zip(&self.regions)
+            .enumerate()
+            .filter(|(other, (after, before))| {
+                after != before && *other != index && (group.is_none() || after.tab_group != group)
+            })
+            .map(|(other, _)| other)
+            .collect()
+    }
+
+    /// Grow the region at `index` by moving its edge facing `direction` outwards by `amount`, pushing the neighbors
+    /// on that side back to make room. A negative `amount` shrinks the region instead, letting the neighbors expand.
+    pub fn grow(&mut self, index: usize, direction: &Direction, amount: i64) -> Result<()> {
+        let resize = match direction {
+            Direction::Up => Resize::Top(-amount),
+            Direction::Down => Resize::Bottom(amount),
+            Direction::Left => Resize::Left(-amount),
+            Direction::Right => Resize::Right(amount),
+        };
+
+        self.resize_region_by_index(index, &resize)
+    }
+
+    /// Grow the region at `index` like `grow`, by `percent` of the width or height of the workspace `bounds` along
+    /// `direction` rounded to the nearest pixel, so that the same resize feels alike on any monitor. A negative
+    /// `percent` shrinks the region instead.
+    pub fn resize_region_percent(
+        &mut self,
+        index: usize,
+        direction: &Direction,
+        percent: f64,
+    ) -> Result<()> {
+        let size = self.bounds().size;
+        let dimension = if direction.is_horizontal() {
+            size.w
+        } else {
+            size.h
+        };
+
+        self.grow(
+            index,
+            direction,
+            (dimension as f64 * percent / 100.0).round() as i64,
+        )
+    }
+
+    /// Grow the region at `index` like `grow`, but take the space from its major adjacent region in `direction`
+    /// alone. Any other neighbors on that side are left in place, so the region comes to overlap them when growing
+    /// or leaves a gap beside them when shrinking, either of which `layout_issues` reports. `NoAdjacentRegions` is
+    /// returned if there is no neighbor in `direction`, and nothing is changed if either region would fall below the
+    /// minimum size.
+    pub fn grow_against_major(
+        &mut self,
+        index: usize,
+        direction: &Direction,
+        amount: i64,
+    ) -> Result<()> {
+        self.record(|workspace| {
+            workspace.region(index)?;
+
+            let index = workspace.tab_leader(index).unwrap_or(index);
+            let mut region = *workspace.regions.get(index).unwrap();
+            let major = workspace
+                .major_adjacent_region(&region, *direction)
+                .ok_or(ErrorKind::NoAdjacentRegions)?;
+            let mut sibling = *workspace.regions.get(major).unwrap();
+            let (min, opposite) = (workspace.min_region_size, direction.opposite());
+            let delta = match direction {
+                Direction::Up | Direction::Left => -amount,
+                Direction::Down | Direction::Right => amount,
+            };
+
+            region.set_edge(*direction, region.edge(*direction) + delta, min)?;
+            sibling.set_edge(opposite, sibling.edge(opposite) + delta, min)?;
+            *workspace.regions.get_mut(index).unwrap() = region;
+            *workspace.regions.get_mut(major).unwrap() = sibling;
+
+            Ok(())
+        })
+    }
+
+    /// Move the edge of the region at `index` facing `edge` to the absolute coordinate `target`, adjusting the
+    /// neighbors on that side as with `resize_region`.
+    pub fn resize_region_to(&mut self, index: usize, edge: Direction, target: i64) -> Result<()> {
+        let delta = target - self.region(index)?.edge(edge);
+        let resize = match edge {
+            Direction::Up => Resize::Top(delta),
+            Direction::Down => Resize::Bottom(delta),
+            Direction::Left => Resize::Left(delta),
+            Direction::Right => Resize::Right(delta),
+        };
+
+        self.resize_region_by_index(index, &resize)
+    }
+
+    /// Close the small gaps and overlaps between tiled regions left behind by drifting edges. Any edge within 2 pixels
+    /// of the workspace bounds is moved onto them, and any edge within 2 pixels of the facing edge of an earlier tiled
+    /// region across from it is moved onto that edge. Nothing is changed if this would leave a region below the
+    /// minimum size.
+    pub fn reconcile_edges(&mut self) -> Result<()> {
+        self.record(|workspace| {
+            let (bounds, min) = (workspace.bounds(), workspace.min_region_size);
+            let mut regions = workspace.regions.clone();
+
+            for index in 0..regions.len() {
+                if regions.get(index).unwrap().float {
+                    continue;
+                }
+
+                for direction in Direction::all() {
+                    let region = regions.get(index).unwrap();
+                    let edge = region.edge(direction);
+                    let bound = bounds.edge(direction);
+                    let target = if i64::abs(edge - bound) <= EDGE_TOLERANCE {
+                        Some(bound)
+                    } else {
+                        regions
+                            .iter()
+                            .take(index)
+                            .filter(|sibling| {
+                                !sibling.float && region.overlaps_across(sibling, direction)
+                            })
+                            .map(|sibling| sibling.edge(direction.opposite()))
+                            .find(|sibling_edge| i64::abs(edge - sibling_edge) <= EDGE_TOLERANCE)
+                    };
+
+                    if let Some(target) = target.filter(|target| *target != edge) {
+                        regions
+                            .get_mut(index)
+                            .unwrap()
+                            .set_edge(direction, target, min)?;
+                    }
+                }
+            }
+
+            workspace.regions = regions;
+
+            Ok(())
+        })
+    }
+
+    /// Evenly distribute space among the tiled regions along the axis of `direction`, so `Left` and `Right` balance
+    /// widths while `Up` and `Down` balance heights. Each stack of regions lying end to end along the axis while
+    /// spanning exactly the same stretch across it is balanced on its own, so edges shared with regions outside the
+    /// stack stay where they are. Remainder pixels go to the earliest regions in each stack, one each. Nothing is
+    /// changed if any region would fall below the minimum size.
+    pub fn equalize(&mut self, direction: &Direction) -> Result<()> {
+        self.record(|workspace| {
+            let (axis, min) = if direction.is_vertical() {
+                (Axis::Vertical, workspace.min_region_size.h as i64)
+            } else {
+                (Axis::Horizontal, workspace.min_region_size.w as i64)
+            };
+            let mut tiled = workspace
+                .tiled_regions()
+                .map(|(index, region)| {
+                    (
+                        index,
+                        region.extent(axis.perpendicular()),
+                        region.extent(axis),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let mut regions = workspace.regions.clone();
+
+            tiled.sort_by_key(|(_, across, along)| (*across, *along));
+
+            for stack in tiled.chunk_by(|(_, across, along), (_, next_across, next_along)| {
+                across == next_across && along.1 == next_along.0
+            }) {
+                let (start, end) = (stack[0].2 .0, stack[stack.len() - 1].2 .1);
+                let count = stack.len() as i64;
+                let (length, remainder) = ((end - start) / count, (end - start) % count);
+
+                if length < min {
+                    return Err(ErrorKind::InvalidRegion);
+                }
+
+                let mut edge = start;
+
+                for (position, (index, _, _)) in stack.iter().enumerate() {
+                    let length = length + i64::from((position as i64) < remainder);
+                    let region = regions.get_mut(*index).unwrap();
+
+                    if direction.is_vertical() {
+                        region.pos.y = edge;
+                        region.size.h = length as u64;
+                    } else {
+                        region.pos.x = edge;
+                        region.size.w = length as u64;
+                    }
+
+                    edge += length;
+                }
+            }
+
+            workspace.regions = regions;
+
+            Ok(())
+        })
+    }
+
+    /// Rotate the geometry of the tiled regions by one slot, so each takes over the geometry of the tiled region before
+    /// it and the first takes over that of the last. When `reverse` is set each region instead takes over the
+    /// geometry of the one after it. Floating regions are left where they are.
+    pub fn cycle_regions(&mut self, reverse: bool) -> Result<()> {
+        self.record(|workspace| {
+            let indices = workspace
+                .tiled_regions()
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>();
+            let mut geometry = indices
+                .iter()
+                .map(|index| {
+                    let region = workspace.regions.get(*index).unwrap();
+
+                    (region.size, region.pos)
+                })
+                .collect::<Vec<_>>();
+
+            if geometry.is_empty() {
+                return Ok(());
+            }
+
+            if reverse {
+                geometry.rotate_left(1);
+            } else {
+                geometry.rotate_right(1);
+            }
+
+            for (index, (size, pos)) in indices.into_iter().zip(geometry) {
+                let region = workspace.regions.get_mut(index).unwrap();
+
+                region.size = size;
+                region.pos = pos;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Round the edges of every tiled region to the nearest multiple of `grid`. Edges that are shared stay shared as
+    /// they snap to the same line, while edges lying on the workspace bounds are left in place so the layout still
+    /// fills the workspace. Nothing is changed if snapping would leave any region below the minimum size.
+    pub fn snap_to_grid(&mut self, grid: u64) -> Result<()> {
+        self.record(|workspace| {
+            if grid <= 1 {
+                return Ok(());
+            }
+
+            let grid = grid as i64;
+            let snap = |edge: i64, (start, end): (i64, i64)| {
+                if edge == start || edge == end {
+                    edge
+                } else {
+                    (edge + grid / 2).div_euclid(grid) * grid
+                }
+            };
+            let bounds = workspace.bounds();
+            let (w, h) = (
+                bounds.extent(Axis::Horizontal),
+                bounds.extent(Axis::Vertical),
+            );
+            let mut regions = workspace.regions.clone();
+
+            for region in regions.iter_mut().filter(|region| !region.float) {
+                let (left, right) = (snap(region.left(), w), snap(region.right(), w));
+                let (top, bottom) = (snap(region.top(), h), snap(region.bottom(), h));
+
+                if right - left < workspace.min_region_size.w as i64
+                    || bottom - top < workspace.min_region_size.h as i64
+                {
+                    return Err(ErrorKind::InvalidRegion);
+                }
+
+                region.pos = Position::new(left, top);
+                region.size = Rectangle::new((right - left) as u64, (bottom - top) as u64);
+            }
+
+            workspace.regions = regions;
+
+            Ok(())
+        })
+    }
+
+    /// Remove the region at `index`, growing its neighbors to reclaim the freed space. If the removed region was
+    /// focused, focus passes to one of the neighbors that reclaimed its space. The side chosen for backfilling is the
+    /// one whose neighbors exactly span the removed region's edge, preferring the side with the fewest neighbors so
+    /// that a region removed right after a split is absorbed by the sibling it was split from. Floating regions are
+    /// removed without backfilling, as is a tiled region that has no cleanly reclaimable side, leaving its space empty.
+    /// A region sharing its tab group with another tiled region leaves no space behind, so it's removed without
+    /// backfilling too. Nothing is changed if a neighbor can't grow to reclaim the space, such as past its `max` size.
+    pub fn remove_region(&mut self, index: usize) -> Result<()> {
+        self.record(|workspace| {
+            if index >= workspace.regions.len() {
+                return Err(ErrorKind::UnknownRegion);
+            }
+
+            let (regions, focused) = (workspace.regions.clone(), workspace.focused_region);
+            let region = workspace.regions.remove(index);
+
+            workspace.focused_region = match workspace.focused_region {
+                Some(focused) if focused == index => None,
+                Some(focused) if focused > index => Some(focused - 1),
+                focused => focused,
+            };
+
+            let shared = region.tab_group.is_some()
+                && workspace
+                    .regions
+                    .iter()
+                    .any(|sibling| !sibling.float && sibling.tab_group == region.tab_group);
+
+            if region.float || shared {
+                return Ok(());
+            }
+
+            match workspace.backfill(&region) {
+                Ok(Some(siblings)) => {
+                    if workspace.focused_region.is_none() {
+                        workspace.focused_region = siblings.first().copied();
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    workspace.regions = regions;
+                    workspace.focused_region = focused;
+
+                    return Err(error);
+                }
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Float the tiled region at `index`, the inverse of `tile_float`. The region is resized to 60% of the workspace
+    /// `bounds`, centered within them, and raised above every other floating region, while its neighbors grow to
+    /// reclaim the space it leaves as with `remove_region`. A region in a tab group leaves the group. `InvalidRegion`
+    /// is returned if the region is already floating, and nothing is changed if the neighbors can't reclaim its space.
+    pub fn float_region(&mut self, index: usize) -> Result<()> {
+        self.record(|workspace| {
+            let region = *workspace.region(index)?;
+
+            if region.float {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            let (bounds, regions) = (workspace.bounds(), workspace.regions.clone());
+            let size = bounds.size.scale(0.6);
+            let float = workspace.regions.get_mut(index).unwrap();
+
+            float.size = size;
+            float.pos = bounds.pos.offset(
+                (bounds.size.w - size.w) as i64 / 2,
+                (bounds.size.h - size.h) as i64 / 2,
+            );
+            float.float = true;
+            float.tab_group = None;
+
+            let shared = region.tab_group.is_some()
+                && workspace
+                    .tiled_regions()
+                    .any(|(_, sibling)| sibling.tab_group == region.tab_group);
+
+            if !shared {
+                if let Err(error) = workspace.backfill(&region) {
+                    workspace.regions = regions;
+
+                    return Err(error);
+                }
+            }
+
+            workspace.raise_region(index)
+        })
+    }
+
+    /// Grow the neighbors of the space taken by `region` to reclaim it, returning the neighbors that did.
+    /// See `remove_region` for how the side to backfill from is chosen. `None` is returned without any such side.
+    fn backfill(&mut self, region: &Region) -> Result<Option<Vec<usize>>> {
+        let backfill = Direction::all()
+            .into_iter()
+            .map(|direction| (direction, self.adjacent_regions(region, direction)))
+            .filter(|(direction, siblings)| {
+                let axis = direction.axis().perpendicular();
+                let (start, end) = region.extent(axis);
+                let mut covered = 0;
+
+                !siblings.is_empty()
+                    && siblings.iter().all(|index| {
+                        let (sibling_start, sibling_end) =
+                            self.regions.get(*index).unwrap().extent(axis);
+
+                        covered += sibling_end - sibling_start;
+
+                        sibling_start >= start && sibling_end <= end
+                    })
+                    && covered == end - start
+            })
+            .min_by_key(|(_, siblings)| siblings.len());
+        let Some((direction, siblings)) = backfill else {
+            return Ok(None);
+        };
+
+        for index in &siblings {
+            let edge = direction.opposite();
+
+            self.regions.get_mut(*index).unwrap().set_edge(
+                edge,
+                region.edge(edge),
+                self.min_region_size,
+            )?;
+        }
+
+        Ok(Some(siblings))
+    }
+
+    /// Add a floating region of `size`, returning its index. The first floating region is centered in the workspace
+    /// `bounds`, and each one after is cascaded down and right from the last so that it doesn't sit exactly on top of
+    /// another, wrapping around to stay within the bounds. The new region is raised above every other floating region.
+    /// `InvalidRegion` is returned if `size` is below the minimum size or doesn't fit in the bounds.
+    pub fn place_float(&mut self, size: Rectangle) -> Result<usize> {
+        self.record(|workspace| {
+            let bounds = workspace.bounds();
+
+            if size.w < workspace.min_region_size.w
+                || size.h < workspace.min_region_size.h
+                || size.w > bounds.size.w
+                || size.h > bounds.size.h
+            {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            let (range_x, range_y) = (
+                (bounds.size.w - size.w) as i64 + 1,
+                (bounds.size.h - size.h) as i64 + 1,
+            );
+            let floats = workspace
+                .floating_regions()
+                .map(|(_, region)| region.pos)
+                .collect::<Vec<_>>();
+            let origin = floats
+                .last()
+                .map(|pos| pos.offset(-bounds.left(), -bounds.top()))
+                .unwrap_or(Position::new(range_x / 2, range_y / 2));
+            let candidate = |step: i64| {
+                bounds.pos.offset(
+                    (origin.x + step * FLOAT_CASCADE).rem_euclid(range_x),
+                    (origin.y + step * FLOAT_CASCADE).rem_euclid(range_y),
+                )
+            };
+            let pos = (0..=floats.len() as i64)
+                .map(candidate)
+                .find(|pos| !floats.contains(pos))
+                .unwrap_or(candidate(0));
+
+            workspace.regions.extend([Region::new(size, pos, true)]);
+
+            let index = workspace.regions.len() - 1;
+
+            workspace.raise_region(index).map(|()| index)
+        })
+    }
+
+    /// Move the floating region at `index` by `delta`, clamping it within the workspace according to `float_clamp`.
+    /// Tiled regions can't be moved freely; `InvalidRegion` is returned for them.
+    pub fn move_region(&mut self, index: usize, delta: Position) -> Result<()> {
+        self.record(|workspace| {
+            let bounds = workspace.bounds();
+            let visible = match workspace.float_clamp {
+                FloatClamp::Inside => u64::MAX,
+                FloatClamp::Partial(visible) => visible,
+            };
+            let region = workspace.region_mut(index)?;
+
+            if !region.float {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            region.pos = region.pos + delta;
+            region.clamp_to(&bounds, visible);
+
+            Ok(())
+        })
+    }
+
+    /// Toggle whether the region at `index` fills the whole workspace, returning whether it is now fullscreen.
+    /// The geometry from before entering fullscreen is kept on the region and restored when leaving it.
+    pub fn toggle_fullscreen(&mut self, index: usize) -> Result<bool> {
+        self.record(|workspace| {
+            let size = workspace.size;
+            let region = workspace.region_mut(index)?;
+
+            match region.fullscreen.take() {
+                Some((size, pos)) => {
+                    region.size = size;
+                    region.pos = pos;
+
+                    Ok(false)
+                }
+                None => {
+                    region.fullscreen = Some((region.size, region.pos));
+                    region.size = size;
+                    region.pos = Position::new(0, 0);
+
+                    Ok(true)
+                }
+            }
+        })
+    }
+
+    /// Make the tiled region at `index` take `ratio` of the width of the workspace `bounds` across their full height,
+    /// with every other tiled region compressed into an evenly divided stack on the right, in index order. The geometry
+    /// from before the first maximize is kept and restored by `unmaximize`. Nothing is changed if `ratio` doesn't lie
+    /// strictly between 0 and 1 or the stack would need regions below the minimum size.
+    pub fn maximize_region(&mut self, index: usize, ratio: f64) -> Result<()> {
+        self.record(|workspace| {
+            if workspace.region(index)?.float || ratio <= 0.0 || ratio >= 1.0 {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            let (bounds, min) = (workspace.bounds(), workspace.min_region_size);
+            let size = bounds.size;
+            let stack = workspace
+                .tiled_regions()
+                .map(|(other, _)| other)
+                .filter(|other| *other != index)
+                .collect::<Vec<_>>();
+            let main = if stack.is_empty() {
+                size.w
+            } else {
+                (size.w as f64 * ratio) as u64
+            };
+            let count = u64::max(stack.len() as u64, 1);
+            let (height, remainder) = (size.h / count, size.h % count);
+
+            if main < min.w || (!stack.is_empty() && (size.w - main < min.w || height < min.h)) {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            if workspace.maximized.is_none() {
+                workspace.maximized = Some(workspace.regions.clone());
+            }
+
+            let region = workspace.regions.get_mut(index).unwrap();
+
+            region.size = Rectangle::new(main, size.h);
+            region.pos = bounds.pos;
+
+            let mut y = 0;
+
+            for (position, other) in stack.into_iter().enumerate() {
+                let h = height + u64::from((position as u64) < remainder);
+                let region = workspace.regions.get_mut(other).unwrap();
+
+                region.size = Rectangle::new(size.w - main, h);
+                region.pos = bounds.pos.offset(main as i64, y);
+                y += h as i64;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Restore the geometry of the tiled regions from before `maximize_region`. `NoHistory` is returned if no region is
+    /// maximized, and `InvalidRegion` if regions have been added or removed since, in which case the kept geometry is
+    /// discarded.
+    pub fn unmaximize(&mut self) -> Result<()> {
+        self.record(|workspace| {
+            let regions = workspace.maximized.take().ok_or(ErrorKind::NoHistory)?;
+
+            if regions.len() != workspace.regions.len() {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            for (region, saved) in workspace.regions.iter_mut().zip(regions) {
+                if saved.float {
+                    continue;
+                }
+
+                region.size = saved.size;
+                region.pos = saved.pos;
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Resize the region at `index` like `resize_region`, but rather than failing when an edge can't move as far as
+    /// requested, move it as far as it legally can and return the deltas that were actually applied.
+    /// Edges of tiled regions are also kept within the workspace, and the `min` and `max` limits of the region and its
+    /// neighbors are respected, along with those of every region in their tab groups. Both edges of a diagonal resize
+    /// are recorded as a single step in the undo history. Nothing is applied for an unknown index.
+    pub fn resize_region_clamped(&mut self, index: usize, resize: &Resize) -> Resize {
+        self.record(|workspace| match *resize {
+            Resize::Top(top) => Resize::Top(workspace.move_edge_clamped(index, Direction::Up, top)),
+            Resize::Bottom(bottom) => {
+                Resize::Bottom(workspace.move_edge_clamped(index, Direction::Down, bottom))
+            }
+            Resize::Left(left) => {
+                Resize::Left(workspace.move_edge_clamped(index, Direction::Left, left))
+            }
+            Resize::Right(right) => {
+                Resize::Right(workspace.move_edge_clamped(index, Direction::Right, right))
+            }
+            Resize::TopLeft(top, left) => Resize::TopLeft(
+                workspace.move_edge_clamped(index, Direction::Up, top),
+                workspace.move_edge_clamped(index, Direction::Left, left),
+            ),
+            Resize::TopRight(top, right) => Resize::TopRight(
+                workspace.move_edge_clamped(index, Direction::Up, top),
+                workspace.move_edge_clamped(index, Direction::Right, right),
+            ),
+            Resize::BottomLeft(bottom, left) => Resize::BottomLeft(
+                workspace.move_edge_clamped(index, Direction::Down, bottom),
+                workspace.move_edge_clamped(index, Direction::Left, left),
+            ),
+            Resize::BottomRight(bottom, right) => Resize::BottomRight(
+                workspace.move_edge_clamped(index, Direction::Down, bottom),
+                workspace.move_edge_clamped(index, Direction::Right, right),
+            ),
+        })
+    }
+
+    /// Move the edge of the region at `index` facing `direction` by up to `delta`, returning the delta applied.
+    fn move_edge_clamped(&mut self, index: usize, direction: Direction, delta: i64) -> i64 {
+        let index = match self.regions.get(index) {
+            Some(_) => self.tab_leader(index).unwrap_or(index),
+            None => return 0,
+        };
+        let region = *self.regions.get(index).unwrap();
+        let dimension = |region: &Region| {
+            if direction.is_vertical() {
+                region.size.h as i64
+            } else {
+                region.size.w as i64
+            }
+        };
+        let min = if direction.is_vertical() {
+            self.min_region_size.h as i64
+        } else {
+            self.min_region_size.w as i64
+        };
+        let limits = |index: usize| {
+            let leader = self.regions.get(index).unwrap();
+
+            self.regions
+                .iter()
+                .enumerate()
+                .filter(|(other, region)| {
+                    *other == index
+                        || (leader.tab_group.is_some()
+                            && !region.float
+                            && region.tab_group == leader.tab_group)
+                })
+                .map(|(_, region)| {
+                    if direction.is_vertical() {
+                        (region.min.map(|min| min.h), region.max.map(|max| max.h))
+                    } else {
+                        (region.min.map(|min| min.w), region.max.map(|max| max.w))
+                    }
+                })
+                .fold((min, i64::MAX), |(min, max), (own_min, own_max)| {
+                    (
+                        i64::max(min, own_min.unwrap_or(0) as i64),
+                        i64::min(max, own_max.map_or(i64::MAX, |max| max as i64)),
+                    )
+                })
+        };
+        let bounds = self.bounds();
+        let (outward, bound) = match direction {
+            Direction::Up => (-1, region.top() - bounds.top()),
+            Direction::Down => (1, bounds.bottom() - region.bottom()),
+            Direction::Left => (-1, region.left() - bounds.left()),
+            Direction::Right => (1, bounds.right() - region.right()),
+        };
+        let siblings = self.resize_neighbors(&region, direction);
+        let (region_min, region_max) = limits(index);
+        let shrink = siblings
+            .iter()
+            .map(|sibling| dimension(self.regions.get(*sibling).unwrap()) - limits(*sibling).1)
+            .chain([region_min - dimension(&region)])
+            .max()
+            .unwrap();
+        let grow = siblings
+            .iter()
+            .map(|sibling| dimension(self.regions.get(*sibling).unwrap()) - limits(*sibling).0)
+            .chain([region_max - dimension(&region)])
+            .chain(if region.float { None } else { Some(bound) })
+            .min()
+            .unwrap();
+        let delta = if shrink > grow {
+            0
+        } else {
+            (delta * outward).clamp(shrink, grow) * outward
+        };
+        let resize = match direction {
+            Direction::Up => Resize::Top(delta),
+            Direction::Down => Resize::Bottom(delta),
+            Direction::Left => Resize::Left(delta),
+            Direction::Right => Resize::Right(delta),
+        };
+
+        match self.resize_region_by_index(index, &resize) {
+            Ok(()) => delta,
+            Err(_) => 0,
+        }
+    }
+
+    /// Swap the geometry of the region at `index` with its major adjacent region in `direction`.
+    pub fn swap_region(&mut self, index: usize, direction: &Direction) -> Result<()> {
+        let sibling = self
+            .major_adjacent_region(self.region(index)?, *direction)
+            .ok_or(ErrorKind::NoAdjacentRegions)?;
+
+        self.swap_regions(index, sibling)
+    }
+
+    /// Swap the geometry of the regions at `a` and `b`, whether or not they are adjacent.
+    pub fn swap_regions(&mut self, a: usize, b: usize) -> Result<()> {
+        self.record(|workspace| {
+            let region = *workspace.region(a)?;
+            let sibling = workspace.region_mut(b)?;
+            let (size, pos) = (sibling.size, sibling.pos);
+
+            sibling.size = region.size;
+            sibling.pos = region.pos;
+
+            let region = workspace.regions.get_mut(a).unwrap();
+
+            region.size = size;
+            region.pos = pos;
+
+            Ok(())
+        })
+    }
+
+    /// Restore the regions to how they were before the most recently recorded operation, along with the geometry kept
+    /// by `maximize_region`, so that undoing a maximize leaves nothing for `unmaximize` to restore.
+    /// Operations changing the regions are only recorded while `history_depth` is non-zero, in which case up to that
+    /// many are kept. Changes made directly to `regions` are never recorded.
+    pub fn undo(&mut self) -> Result<()> {
+        let (regions, maximized) = self.undo_history.pop().ok_or(ErrorKind::NoHistory)?;
+
+        self.redo_history.extend([(
+            std::mem::replace(&mut self.regions, regions),
+            std::mem::replace(&mut self.maximized, maximized),
+        )]);
+        self.restore_focus();
+
+        Ok(())
+    }
+
+    /// Reapply the most recently undone operation.
+    pub fn redo(&mut self) -> Result<()> {
+        let (regions, maximized) = self.redo_history.pop().ok_or(ErrorKind::NoHistory)?;
+
+        self.undo_history.extend([(
+            std::mem::replace(&mut self.regions, regions),
+            std::mem::replace(&mut self.maximized, maximized),
+        )]);
+        self.restore_focus();
+
+        Ok(())
+    }
+
+    /// Run `operation`, recording the prior regions in the undo history if it changed them. The history is set aside
+    /// while `operation` runs so that any recorded operations it performs internally are folded into a single entry.
+    /// Afterwards, the regions in tab groups are brought back in line with their leaders.
+    fn record<T>(&mut self, operation: impl FnOnce(&mut Self) -> T) -> T {
+        let operation = |workspace: &mut Self| {
+            let result = operation(workspace);
+
+            workspace.sync_tab_groups();
+            result
+        };
+
+        if self.history_depth == 0 {
+            return operation(self);
+        }
+
+        let undo_history = std::mem::take(&mut self.undo_history);
+        let redo_history = std::mem::take(&mut self.redo_history);
+        let (regions, maximized) = (self.regions.clone(), self.maximized.clone());
+        let result = operation(self);
+
+        self.undo_history = undo_history;
+        self.redo_history = redo_history;
+
+        if self.regions != regions {
+            self.undo_history.extend([(regions, maximized)]);
+            self.redo_history.clear();
+
+            if self.undo_history.len() > self.history_depth {
+                self.undo_history.remove(0);
+            }
+        }
+
+        result
+    }
+
+    fn restore_focus(&mut self) {
+        if self.focused_region >= Some(self.regions.len()) {
+            self.focused_region = None;
+        }
+    }
+
+    /// Resize the workspace, scaling every region proportionally from the old `bounds` into the new ones, with the
+    /// `struts` kept as they are. Edges rather than sizes are scaled so that any edge shared by two regions before
+    /// the resize is still shared afterwards. Fullscreen regions are resized to fill the new workspace, with the
+    /// geometry kept for leaving fullscreen scaled like any other region. Nothing is changed if scaling would shrink
+    /// any region below the minimum size. The undo history and any geometry kept by `maximize_region` are cleared, as
+    /// the recorded regions no longer fit the workspace.
+    pub fn resize(&mut self, new: Rectangle) -> Result<()> {
+        let (old, bounds) = (self.bounds(), Self::bounds_within(new, self.struts));
+        let scale_w = bounds.size.w as f64 / old.size.w as f64;
+        let scale_h = bounds.size.h as f64 / old.size.h as f64;
+        let scale = |edge: i64, from: i64, to: i64, scale: f64| {
+            to + ((edge - from) as f64 * scale).round() as i64
+        };
+        let min = self.min_region_size;
+        let scale_geometry = |size: Rectangle, pos: Position| {
+            let region = Region::new(size, pos, false);
+            let (left, right) = (
+                scale(region.left(), old.left(), bounds.left(), scale_w),
+                scale(region.right(), old.left(), bounds.left(), scale_w),
+            );
+            let (top, bottom) = (
+                scale(region.top(), old.top(), bounds.top(), scale_h),
+                scale(region.bottom(), old.top(), bounds.top(), scale_h),
+            );
+
+            if right - left < min.w as i64 || bottom - top < min.h as i64 {
+                return Err(ErrorKind::InvalidRegion);
+            }
+
+            Ok((
+                Rectangle::new((right - left) as u64, (bottom - top) as u64),
+                Position::new(left, top),
+            ))
+        };
+        let mut regions = self.regions.clone();
+
+        for region in &mut regions {
+            match region.fullscreen {
+                Some((size, pos)) => {
+                    region.fullscreen = Some(scale_geometry(size, pos)?);
+                    region.size = new;
+                    region.pos = Position::new(0, 0);
+                }
+                None => {
+                    let (size, pos) = scale_geometry(region.size, region.pos)?;
+
+                    region.size = size;
+                    region.pos = pos;
+                }
+            }
+        }
+
+        self.regions = regions;
+        self.size = new;
+        self.undo_history.clear();
+        self.redo_history.clear();
+        self.maximized = None;
+
+        Ok(())
+    }
+
+    /// Transpose the layout for a workspace rotated between landscape and portrait, swapping the horizontal and
+    /// vertical position and size of every region within the `bounds` so that side-by-side regions become stacked,
+    /// then resizing the workspace to `new_size` as with `resize`. Nothing is changed if any region would end up below
+    /// the minimum size.
+    pub fn rotate_layout(&mut self, new_size: Rectangle) -> Result<()> {
+        let (mut workspace, bounds) = (self.clone(), self.bounds());
+        let (top, bottom, left, right) = self.struts;
+        let transpose = |size: Rectangle, pos: Position| {
+            let pos = pos.offset(-bounds.left(), -bounds.top());
+
+            (
+                Rectangle::new(size.h, size.w),
+                bounds.pos.offset(pos.y, pos.x),
+            )
+        };
+
+        workspace.size = Rectangle::new(bounds.size.h + left + right, bounds.size.w + top + bottom);
+
+        for region in &mut workspace.regions {
+            let (size, pos) = transpose(region.size, region.pos);
+
+            region.size = size;
+            region.pos = pos;
+            region.fullscreen = region.fullscreen.map(|(size, pos)| transpose(size, pos));
+        }
+
+        workspace.resize(new_size)?;
+        *self = workspace;
+
+        Ok(())
+    }
+}
+
+impl Monitor {
+    pub fn new(size: Rectangle, pos: Position) -> Self {
+        Self { size, pos }
+    }
+
+    /// Whether `pos` lies on the monitor, with the right and bottom edges being exclusive as with `Region::contains`.
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.x >= self.pos.x
+            && pos.x < self.pos.x + self.size.w as i64
+            && pos.y >= self.pos.y
+            && pos.y < self.pos.y + self.size.h as i64
+    }
+}
+
+impl Resize {
+    /// The resize that undoes this one, with every delta negated.
+    pub fn invert(self) -> Self {
+        match self {
+            Resize::Top(top) => Resize::Top(-top),
+            Resize::Bottom(bottom) => Resize::Bottom(-bottom),
+            Resize::Left(left) => Resize::Left(-left),
+            Resize::Right(right) => Resize::Right(-right),
+            Resize::TopLeft(top, left) => Resize::TopLeft(-top, -left),
+            Resize::TopRight(top, right) => Resize::TopRight(-top, -right),
+            Resize::BottomLeft(bottom, left) => Resize::BottomLeft(-bottom, -left),
+            Resize::BottomRight(bottom, right) => Resize::BottomRight(-bottom, -right),
+        }
+    }
+}
+
+impl Manager {
+    pub fn add_monitor(&mut self, size: Rectangle, pos: Position) -> usize {
+        self.monitors.extend([Monitor::new(size, pos)]);
+
+        self.monitors.len() - 1
+    }
+
+    /// Remove the monitor at `index`, first moving each of its workspaces to the monitor at `fallback` and resizing
+    /// them to fit. Monitors after `index` shift down by one, and the workspaces on them are updated to match.
+    /// `UnknownMonitor` is returned if either monitor doesn't exist, and `InvalidMonitor` if `fallback` is `index`
+    /// itself, so the last monitor can't be removed. Nothing is changed if any of the workspaces can't be resized to
+    /// fit `fallback`.
+    pub fn remove_monitor(&mut self, index: usize, fallback: usize) -> Result<()> {
+        if index >= self.monitors.len() {
+            return Err(ErrorKind::UnknownMonitor);
+        }
+
+        if fallback == index {
+            return Err(ErrorKind::InvalidMonitor);
+        }
+
+        let size = self
+            .monitors
+            .get(fallback)
+            .ok_or(ErrorKind::UnknownMonitor)?
+            .size;
+        let mut workspaces = self.workspaces.clone();
+
+        for workspace in &mut workspaces {
+            if workspace.monitor == Some(index) {
+                workspace.resize(size)?;
+                workspace.monitor = Some(fallback);
+            }
+
+            workspace.monitor = workspace.monitor.map(|monitor| {
+                if monitor > index {
+                    monitor - 1
+                } else {
+                    monitor
+                }
+            });
+        }
+
+        self.workspaces = workspaces;
+        self.monitors.remove(index);
+
+        Ok(())
+    }
+
+    pub fn monitor_at(&self, pos: Position) -> Option<usize> {
+        self.monitors
+            .iter()
+            .position(|monitor| monitor.contains(pos))
+    }
+
+    /// Find the workspace holding a region equal to `region`, returning the indices of the workspace and the region.
+    /// Regions are plain values compared by equality, so when several are identical, such as tabs in the same group or
+    /// the sole regions of equally sized workspaces, the first match by workspace and then region index is returned.
+    pub fn find_region(&self, region: &Region) -> Option<(usize, usize)> {
+        self.workspaces
+            .iter()
+            .enumerate()
+            .find_map(|(index, workspace)| {
+                workspace
+                    .find_region(|other| other == region)
+                    .map(|found| (index, found))
+            })
+    }
+
+    /// The smallest region covering every monitor, in global coordinates. `None` is returned without any monitors.
+    pub fn total_bounds(&self) -> Option<Region> {
+        let (left, top, right, bottom) = self.monitors.iter().fold(None, |bounds, monitor| {
+            let (right, bottom) = (
+                monitor.pos.x + monitor.size.w as i64,
+                monitor.pos.y + monitor.size.h as i64,
+            );
+
+            Some(match bounds {
+                None => (monitor.pos.x, monitor.pos.y, right, bottom),
+                Some((l, t, r, b)) => (
+                    i64::min(l, monitor.pos.x),
+                    i64::min(t, monitor.pos.y),
+                    i64::max(r, right),
+                    i64::max(b, bottom),
+                ),
+            })
+        })?;
+
+        Some(Region::new(
+            Rectangle::new((right - left) as u64, (bottom - top) as u64),
+            Position::new(left, top),
+            false,
+        ))
+    }
+
+    /// A compact, human-readable dump of every monitor, workspace and region for debugging.
+    pub fn summary(&self) -> String {
+        use std::fmt::Write;
+
+        let mut summary = String::new();
+
+        writeln!(summary, "monitors: {}", self.monitors.len()).unwrap();
+
+        for (index, monitor) in self.monitors.iter().enumerate() {
+            writeln!(
+                summary,
+                "  {index}: {}x{} at ({}, {})",
+                monitor.size.w, monitor.size.h, monitor.pos.x, monitor.pos.y
+            )
+            .unwrap();
+        }
+
+        writeln!(summary, "workspaces: {}", self.workspaces.len()).unwrap();
+
+        for (index, workspace) in self.workspaces.iter().enumerate() {
+            let monitor = match workspace.monitor {
+                Some(monitor) => format!("monitor {monitor}"),
+                None => "no monitor".to_string(),
+            };
+
+            writeln!(
+                summary,
+                "  {index} (id {}) on {monitor}: {}x{}, regions: {}",
+                workspace.id.0,
+                workspace.size.w,
+                workspace.size.h,
+                workspace.regions.len()
+            )
+            .unwrap();
+
+            for (index, region) in workspace.regions.iter().enumerate() {
+                writeln!(
+                    summary,
+                    "    {index}: {}x{} at ({}, {}){}",
+                    region.size.w,
+                    region.size.h,
+                    region.pos.x,
+                    region.pos.y,
+                    if region.float { " float" } else { "" }
+                )
+                .unwrap();
+            }
+        }
+
+        summary
+    }
+
+    /// Find the nearest monitor lying entirely beyond the edge of the monitor at `from` facing `direction` and
+    /// overlapping it across that edge, such as to move focus to it. Monitors lying diagonally don't count. Monitors
+    /// the same distance away are ordered by how close their centers are across that edge, then by index.
+    /// `NoAdjacentRegions` is returned if there is no monitor in `direction`.
+    pub fn focus_monitor(&self, from: usize, direction: &Direction) -> Result<usize> {
+        let bounds = |monitor: &Monitor| Region::new(monitor.size, monitor.pos, false);
+        let start = bounds(self.monitors.get(from).ok_or(ErrorKind::UnknownMonitor)?);
+        let axis = direction.axis().perpendicular();
+        let center = |region: &Region| {
+            let (start, end) = region.extent(axis);
+
+            start + end
+        };
+
+        self.monitors
+            .iter()
+            .map(bounds)
+            .enumerate()
+            .filter(|(index, monitor)| *index != from && start.overlaps_across(monitor, *direction))
+            .map(|(index, monitor)| {
+                let distance = match direction {
+                    Direction::Up | Direction::Left => {
+                        start.edge(*direction) - monitor.edge(direction.opposite())
+                    }
+                    Direction::Down | Direction::Right => {
+                        monitor.edge(direction.opposite()) - start.edge(*direction)
+                    }
+                };
+
+                (index, distance, i64::abs(center(&monitor) - center(&start)))
+            })
+            .filter(|(_, distance, _)| *distance >= 0)
+            .min_by_key(|(index, distance, offset)| (*distance, *offset, *index))
+            .map(|(index, _, _)| index)
+            .ok_or(ErrorKind::NoAdjacentRegions)
+    }
+
+    /// Iterate over the indices of the workspaces shown on the monitor at `monitor`.
+    pub fn workspaces_on_monitor(&self, monitor: usize) -> impl Iterator<Item = usize> + '_ {
+        self.workspaces
+            .iter()
+            .enumerate()
+            .filter(move |(_, workspace)| workspace.monitor == Some(monitor))
+            .map(|(index, _)| index)
+    }
+
+    /// Create a new workspace sized to fit `monitor`, returning its index in `workspaces`.
+    /// The workspace is also assigned a fresh `WorkspaceId` which, unlike the index, remains valid across removals.
+    /// `InvalidRegion` is returned if the monitor is smaller than the minimum region size.
+    pub fn create_workspace(&mut self, monitor: &Monitor) -> Result<usize> {
+        let index = self
+            .monitors
+            .iter()
+            .position(|x| x == monitor)
+            .ok_or(ErrorKind::UnknownMonitor)?;
+        let mut workspace = Workspace::try_new(monitor.size)?;
+
+        self.next_workspace_id += 1;
+        workspace.monitor = Some(index);
+        workspace.id = WorkspaceId(self.next_workspace_id);
+
+        self.workspaces.extend([workspace]);
+
+        Ok(self.workspaces.len() - 1)
+    }
+
+    /// Remove the workspace at `index`, returning it so that its regions can be migrated elsewhere.
+    /// Workspaces after `index` shift down by one, so any indices held by the caller past it are invalidated.
+    pub fn remove_workspace(&mut self, index: usize) -> Result<Workspace> {
+        if index >= self.workspaces.len() {
+            return Err(ErrorKind::UnknownWorkspace);
+        }
+
+        self.focused_workspace = match self.focused_workspace {
+            Some(focused) if focused == index => None,
+            Some(focused) if focused > index => Some(focused - 1),
+            focused => focused,
+        };
+
+        Ok(self.workspaces.remove(index))
+    }
+
+    /// Convert the position of a region from its workspace's local coordinates into the global coordinate space
+    /// shared by all monitors. `UnknownMonitor` is returned if the workspace isn't associated with a monitor.
+    pub fn region_global_pos(&self, workspace: usize, region: usize) -> Result<Position> {
+        let workspace = self
+            .workspaces
+            .get(workspace)
+            .ok_or(ErrorKind::UnknownWorkspace)?;
+        let region = workspace.region(region)?;
+        let monitor = workspace
+            .monitor
+            .and_then(|monitor| self.monitors.get(monitor))
+            .ok_or(ErrorKind::UnknownMonitor)?;
+
+        Ok(monitor.pos + region.pos)
+    }
+
+    /// Merge the workspace at `from` into the workspace at `into`, such as when the monitor showing `from` is
+    /// unplugged. The regions of `from` are rescaled to fit `into` and added as floating regions, since there's no
+    /// single sensible way to merge two tiled layouts, before `from` is removed. Indices past `from` shift down as with
+    /// `remove_workspace`. The merged regions leave any tab groups they were in and are raised above the floating
+    /// regions of `into`, with the regions that were floating in `from` kept above the tiled ones in their existing
+    /// order. `into` records the merge in its undo history. Merging a workspace into itself does nothing.
+    pub fn merge_workspaces(&mut self, from: usize, into: usize) -> Result<()> {
+        if from >= self.workspaces.len() || into >= self.workspaces.len() {
+            return Err(ErrorKind::UnknownWorkspace);
+        }
+
+        if from == into {
+            return Ok(());
+        }
+
+        let size = self.workspaces.get(into).unwrap().size;
+
+        self.workspaces.get_mut(from).unwrap().resize(size)?;
+
+        let focused = self.focused_workspace == Some(from);
+        let source = self.remove_workspace(from)?;
+        let into = if into > from { into - 1 } else { into };
+        let target = self.workspaces.get_mut(into).unwrap();
+        let offset = target.regions.len();
+        let mut stacking = (offset..offset + source.regions.len())
+            .zip(&source.regions)
+            .map(|(index, region)| (region.float, region.z, index))
+            .collect::<Vec<_>>();
+
+        stacking.sort();
+        target.record(|workspace| {
+            workspace
+                .regions
+                .extend(source.regions.iter().copied().map(|mut region| {
+                    region.float = true;
+                    region.tab_group = None;
+
+                    region
+                }));
+
+            stacking
+                .into_iter()
+                .try_for_each(|(_, _, index)| workspace.raise_region(index))
+        })?;
+
+        if focused {
+            self.focused_workspace = Some(into);
+            target.focused_region = source
+                .focused_region
+                .map(|region| region + offset)
+                .or(target.focused_region);
+        }
+
+        Ok(())
+    }
+
+    /// Move the region at `region` in the workspace at `from_ws` into the workspace at `to_ws`, returning its new
+    /// index. The region's neighbors in `from_ws` reclaim its space as with `Workspace::remove_region`, and it's added
+    /// to `to_ws` as a floating region raised above the others, with its geometry rescaled between the two workspace
+    /// `bounds` to keep its relative position and size. Both workspaces record the move in their undo history. If the
+    /// region was focused, focus moves with it. `InvalidRegion` is returned if `from_ws` has no space for tiling, and
+    /// nothing is changed if the rescaled region would be below the minimum size. Moving a region into its own
+    /// workspace does nothing and returns its index, as with `merge_workspaces`.
+    pub fn move_region(&mut self, from_ws: usize, region: usize, to_ws: usize) -> Result<usize> {
+        if from_ws == to_ws {
+            let workspace = self
+                .workspaces
+                .get(from_ws)
+                .ok_or(ErrorKind::UnknownWorkspace)?;
+
+            return workspace.region(region).map(|_| region);
+        }
+
+        let target = self
+            .workspaces
+            .get(to_ws)
+            .ok_or(ErrorKind::UnknownWorkspace)?;
+        let (bounds, min) = (target.bounds(), target.min_region_size);
+        let source = self
+            .workspaces
+            .get(from_ws)
+            .ok_or(ErrorKind::UnknownWorkspace)?;
+        let (mut moved, old) = (*source.region(region)?, source.bounds());
+
+        if old.size.w == 0 || old.size.h == 0 {
+            return Err(ErrorKind::InvalidRegion);
+        }
+
+        let scale_w = bounds.size.w as f64 / old.size.w as f64;
+        let scale_h = bounds.size.h as f64 / old.size.h as f64;
+        let scale = |edge: i64, from: i64, to: i64, scale: f64| {
+            to + ((edge - from) as f64 * scale).round() as i64
+        };
+        let (left, right) = (
+            scale(moved.left(), old.left(), bounds.left(), scale_w),
+            scale(moved.right(), old.left(), bounds.left(), scale_w),
+        );
+        let (top, bottom) = (
+            scale(moved.top(), old.top(), bounds.top(), scale_h),
+            scale(moved.bottom(), old.top(), bounds.top(), scale_h),
+        );
+        let focused =
+            self.focused_workspace == Some(from_ws) && source.focused_region == Some(region);
+
+        if right - left < min.w as i64 || bottom - top < min.h as i64 {
+            return Err(ErrorKind::InvalidRegion);
+        }
+
+        moved.pos = Position::new(left, top);
+        moved.size = Rectangle::new((right - left) as u64, (bottom - top) as u64);
+        moved.float = true;
+        moved.tab_group = None;
+
+        self.workspaces
+            .get_mut(from_ws)
+            .unwrap()
+            .remove_region(region)?;
+
+        let index = self
+            .workspaces
+            .get_mut(to_ws)
+            .unwrap()
+            .record(|workspace| {
+                workspace.regions.extend([moved]);
+
+                let index = workspace.regions.len() - 1;
+
+                workspace.raise_region(index).map(|()| index)
+            })?;
+
+        if focused {
+            self.focus(to_ws, index)?;
+        }
+
+        Ok(index)
+    }
+
+    /// Focus the region at `region` within the workspace at `workspace`.
+    pub fn focus(&mut self, workspace: usize, region: usize) -> Result<()> {
+        let target = self
+            .workspaces
+            .get_mut(workspace)
+            .ok_or(ErrorKind::UnknownWorkspace)?;
+
+        if region >= target.regions.len() {
+            return Err(ErrorKind::UnknownRegion);
+        }
+
+        target.focused_region = Some(region);
+        self.focused_workspace = Some(workspace);
+
+        Ok(())
+    }
+
+    /// The indices of the focused workspace and its focused region, if any.
+    pub fn focused(&self) -> Option<(usize, usize)> {
+        let workspace = self.focused_workspace?;
+
+        Some((workspace, self.workspaces.get(workspace)?.focused_region?))
+    }
+
+    /// Move a workspace onto another monitor, rescaling its regions to fit.
+    pub fn move_workspace(&mut self, workspace: usize, monitor: usize) -> Result<()> {
+        let size = self
+            .monitors
+            .get(monitor)
+            .ok_or(ErrorKind::UnknownMonitor)?
+            .size;
+        let workspace = self
+            .workspaces
+            .get_mut(workspace)
+            .ok_or(ErrorKind::UnknownWorkspace)?;
+
+        workspace.resize(size)?;
+        workspace.monitor = Some(monitor);
+
+        Ok(())
+    }
+
+    pub fn workspace(&self, id: WorkspaceId) -> Result<&Workspace> {
+        Ok(self.workspaces.get(self.workspace_index(id)?).unwrap())
+    }
+
+    pub fn workspace_mut(&mut self, id: WorkspaceId) -> Result<&mut Workspace> {
+        let index = self.workspace_index(id)?;
+
+        Ok(self.workspaces.get_mut(index).unwrap())
+    }
+
+    pub fn workspace_index(&self, id: WorkspaceId) -> Result<usize> {
+        self.workspaces
+            .iter()
+            .position(|x| x.id == id)
+            .ok_or(ErrorKind::UnknownWorkspace)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split_workspace() -> Workspace {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.create_region(0, &Direction::Right).unwrap();
+        workspace
+    }
+
+    fn float_beside_split() -> Workspace {
+        let mut workspace = split_workspace();
+
+        workspace.regions.extend([Region::new(
+            Rectangle::new(200, 200),
+            Position::new(300, 100),
+            true,
+        )]);
+        workspace
+    }
+
+    fn stacked_floats() -> Workspace {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        for _ in 0..3 {
+            workspace.place_float(Rectangle::new(200, 200)).unwrap();
+        }
+
+        workspace
+    }
+
+    fn manager_with_workspaces(count: usize) -> Manager {
+        let mut manager = Manager::default();
+        let monitor = manager.add_monitor(Rectangle::new(1000, 500), Position::new(0, 0));
+        let monitor = *manager.monitors.get(monitor).unwrap();
+
+        for _ in 0..count {
+            manager.create_workspace(&monitor).unwrap();
+        }
+
+        manager
+    }
+
+    fn quad_workspace() -> Workspace {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.grid_layout(2, 2).unwrap();
+        workspace
+    }
+
+    fn three_columns() -> Workspace {
+        let mut workspace = Workspace::new(Rectangle::new(900, 600));
+
+        workspace.grid_layout(1, 3).unwrap();
+        workspace
+    }
+
+    #[test]
+    fn create_workspace_unknown_monitor() {
+        let mut manager = Manager::default();
+        let monitor = Monitor::new(Rectangle::new(1920, 1080), Position::new(0, 0));
+
+        assert_eq!(
+            manager.create_workspace(&monitor),
+            Err(ErrorKind::UnknownMonitor)
+        );
+        assert!(manager.workspaces.is_empty());
+    }
+
+    #[test]
+    fn create_workspace_inherits_monitor_size() {
+        let mut manager = Manager::default();
+        let index = manager.add_monitor(Rectangle::new(1920, 1080), Position::new(0, 0));
+        let monitor = *manager.monitors.get(index).unwrap();
+        let workspace = manager.create_workspace(&monitor).unwrap();
+        let workspace = manager.workspaces.get(workspace).unwrap();
+
+        assert_eq!(workspace.size, Rectangle::new(1920, 1080));
+        assert_eq!(workspace.monitor, Some(index));
+        assert_eq!(
+            workspace.regions,
+            vec![Region::new(
+                Rectangle::new(1920, 1080),
+                Position::new(0, 0),
+                false
+            )]
+        );
+    }
+
+    #[test]
+    fn remove_workspace_shifts_indices() {
+        let manager = manager_with_workspaces(3);
+        let ids = manager
+            .workspaces
+            .iter()
+            .map(|workspace| workspace.id)
+            .collect::<Vec<_>>();
+
+        for index in 0..3 {
+            let mut manager = manager.clone();
+            let removed = manager.remove_workspace(index).unwrap();
+            let mut remaining = ids.clone();
+
+            remaining.remove(index);
+
+            assert_eq!(removed.id, ids[index]);
+            assert_eq!(
+                manager
+                    .workspaces
+                    .iter()
+                    .map(|workspace| workspace.id)
+                    .collect::<Vec<_>>(),
+                remaining
+            );
+        }
+    }
+
+    #[test]
+    fn remove_workspace_unknown() {
+        let mut manager = manager_with_workspaces(2);
+
+        assert_eq!(
+            manager.remove_workspace(2).map(|_| ()),
+            Err(ErrorKind::UnknownWorkspace)
+        );
+        assert_eq!(manager.workspaces.len(), 2);
+    }
+
+    #[test]
+    fn removing_workspace_keeps_other_ids() {
+        let mut manager = manager_with_workspaces(2);
+        let (a, b) = (manager.workspaces[0].id, manager.workspaces[1].id);
+
+        assert_ne!(a, b);
+
+        manager.remove_workspace(0).unwrap();
+
+        assert_eq!(manager.workspace(b).unwrap().id, b);
+        assert_eq!(manager.workspace_index(b), Ok(0));
+        assert_eq!(manager.workspace(a), Err(ErrorKind::UnknownWorkspace));
+    }
+
+    #[test]
+    fn unmanaged_workspace_ids_do_not_collide() {
+        let mut manager = manager_with_workspaces(1);
+        let id = manager.workspaces[0].id;
+
+        manager
+            .workspaces
+            .extend([Workspace::new(Rectangle::new(1000, 500))]);
+
+        assert_ne!(manager.workspaces[1].id, id);
+        assert_eq!(manager.workspace_index(id), Ok(0));
+
+        let ids = manager
+            .workspaces
+            .iter()
+            .map(|workspace| workspace.id)
+            .collect::<std::collections::HashSet<_>>();
+
+        assert_eq!(ids.len(), 2);
+    }
+
+    #[test]
+    fn create_workspace_on_second_monitor() {
+        let mut manager = Manager::default();
+
+        manager.add_monitor(Rectangle::new(1920, 1080), Position::new(0, 0));
+
+        let second = manager.add_monitor(Rectangle::new(1280, 1024), Position::new(1920, 0));
+        let monitor = *manager.monitors.get(second).unwrap();
+        let index = manager.create_workspace(&monitor).unwrap();
+        let workspace = manager.workspaces.get(index).unwrap();
+
+        assert_eq!(second, 1);
+        assert_eq!(monitor.pos, Position::new(1920, 0));
+        assert_eq!(workspace.monitor, Some(second));
+        assert_eq!(workspace.size, Rectangle::new(1280, 1024));
+    }
+
+    #[test]
+    fn adjacency_requires_coincident_edges() {
+        let mut workspace = split_workspace();
+        let region = *workspace.region(0).unwrap();
+
+        assert_eq!(
+            workspace.shared_edge_regions(&region, Direction::Right),
+            vec![1]
+        );
+        assert_eq!(
+            workspace.adjacent_regions(&region, Direction::Right),
+            vec![1]
+        );
+
+        workspace
+            .regions
+            .get_mut(1)
+            .unwrap()
+            .set_left(501, MIN_REGION_SIZE)
+            .unwrap();
+
+        assert!(workspace
+            .shared_edge_regions(&region, Direction::Right)
+            .is_empty());
+        assert!(workspace
+            .adjacent_regions(&region, Direction::Right)
+            .is_empty());
+    }
+
+    #[test]
+    fn adjacency_requires_overlap() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.grid_layout(2, 2).unwrap();
+
+        let region = *workspace.region(0).unwrap();
+
+        assert_eq!(
+            workspace.shared_edge_regions(&region, Direction::Right),
+            vec![1, 3]
+        );
+        assert_eq!(
+            workspace.adjacent_regions(&region, Direction::Right),
+            vec![1]
+        );
+
+        let straddling = Region::new(Rectangle::new(500, 100), Position::new(0, 200), false);
+
+        assert_eq!(
+            workspace.adjacent_regions(&straddling, Direction::Right),
+            vec![1, 3]
+        );
+    }
+
+    #[test]
+    fn remove_region_backfills_sibling() {
+        let mut workspace = split_workspace();
+
+        workspace.remove_region(1).unwrap();
+
+        assert_eq!(
+            workspace.regions,
+            Workspace::new(Rectangle::new(1000, 500)).regions
+        );
+        assert!(workspace.layout_issues().is_empty());
+    }
+
+    #[test]
+    fn remove_region_backfills_several_siblings() {
+        let mut workspace = split_workspace();
+
+        workspace.create_region(1, &Direction::Down).unwrap();
+        workspace.remove_region(0).unwrap();
+
+        assert_eq!(workspace.region_count(), 2);
+
+        for region in &workspace.regions {
+            assert_eq!(region.left(), 0);
+            assert_eq!(region.size.w, 1000);
+        }
+
+        assert!(workspace.layout_issues().is_empty());
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn remove_region_restores_earlier_siblings() {
+        let mut workspace = split_workspace();
+
+        workspace.create_region(1, &Direction::Down).unwrap();
+        workspace.regions.get_mut(2).unwrap().max = Some(Rectangle::new(700, 250));
+
+        let before = workspace.clone();
+
+        assert_eq!(workspace.remove_region(0), Err(ErrorKind::InvalidRegion));
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn remove_region_unknown() {
+        let mut workspace = split_workspace();
+
+        assert_eq!(workspace.remove_region(2), Err(ErrorKind::UnknownRegion));
+        assert_eq!(workspace.region_count(), 2);
+    }
+
+    #[test]
+    fn region_contains_boundary_pixels() {
+        let region = Region::new(Rectangle::new(100, 50), Position::new(10, 20), false);
+
+        assert!(region.contains(Position::new(10, 20)));
+        assert!(region.contains(Position::new(109, 69)));
+        assert!(!region.contains(Position::new(110, 20)));
+        assert!(!region.contains(Position::new(10, 70)));
+        assert!(!region.contains(Position::new(9, 20)));
+    }
+
+    #[test]
+    fn region_at_shared_edge_and_floats() {
+        let mut workspace = split_workspace();
+
+        assert_eq!(workspace.region_at(Position::new(499, 0)), Some(0));
+        assert_eq!(workspace.region_at(Position::new(500, 0)), Some(1));
+        assert_eq!(workspace.region_at(Position::new(1000, 0)), None);
+
+        workspace.regions.extend([Region::new(
+            Rectangle::new(200, 200),
+            Position::new(400, 100),
+            true,
+        )]);
+
+        assert_eq!(workspace.region_at(Position::new(450, 150)), Some(2));
+        assert_eq!(workspace.region_at(Position::new(550, 150)), Some(2));
+        assert_eq!(workspace.region_at(Position::new(600, 150)), Some(1));
+    }
+
+    #[test]
+    fn touching_regions_do_not_intersect() {
+        let a = Region::new(Rectangle::new(100, 100), Position::new(0, 0), false);
+        let b = Region::new(Rectangle::new(100, 100), Position::new(100, 0), false);
+
+        assert!(!a.intersects(&b));
+        assert_eq!(a.overlap_area(&b), 0);
+    }
+
+    #[test]
+    fn contained_region_overlaps_fully() {
+        let outer = Region::new(Rectangle::new(100, 100), Position::new(0, 0), false);
+        let inner = Region::new(Rectangle::new(30, 40), Position::new(10, 10), false);
+
+        assert!(outer.intersects(&inner));
+        assert_eq!(outer.overlap_area(&inner), 1200);
+        assert_eq!(inner.overlap_area(&outer), 1200);
+    }
+
+    #[test]
+    fn partial_overlap_area() {
+        let a = Region::new(Rectangle::new(100, 100), Position::new(0, 0), false);
+        let b = Region::new(Rectangle::new(100, 100), Position::new(60, 70), false);
+
+        assert!(a.intersects(&b));
+        assert_eq!(a.overlap_area(&b), 40 * 30);
+    }
+
+    #[test]
+    fn resize_moves_neighbor_edges_with_subject() {
+        let mut workspace = Workspace::new(Rectangle::new(900, 900));
+
+        workspace.grid_layout(3, 3).unwrap();
+
+        for (resize, neighbor, edge, expected) in [
+            (Resize::Top(-50), 1, Direction::Down, 250),
+            (Resize::Bottom(50), 7, Direction::Up, 650),
+            (Resize::Left(-50), 3, Direction::Right, 250),
+            (Resize::Right(50), 5, Direction::Left, 650),
+        ] {
+            let mut workspace = workspace.clone();
+
+            workspace.resize_region_by_index(4, &resize).unwrap();
+
+            assert_eq!(workspace.region(4).unwrap().edge(edge.opposite()), expected);
+            assert_eq!(workspace.region(neighbor).unwrap().edge(edge), expected);
+            assert!(workspace.layout_issues().is_empty());
+        }
+    }
+
+    #[test]
+    fn configurable_minimum_region_size() {
+        let mut workspace = split_workspace();
+
+        workspace.min_region_size = Rectangle::new(50, 50);
+
+        assert_eq!(
+            workspace.resize_region_by_index(0, &Resize::Right(-451)),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(
+            workspace.resize_region_by_index(0, &Resize::Right(451)),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(workspace.region(0).unwrap().size.w, 500);
+        assert_eq!(
+            workspace.resize_region_by_index(0, &Resize::Right(-450)),
+            Ok(())
+        );
+        assert_eq!(workspace.region(0).unwrap().size.w, 50);
+    }
+
+    #[test]
+    fn resize_keeps_shared_edge_at_odd_size() {
+        let mut workspace = split_workspace();
+
+        workspace.resize(Rectangle::new(999, 333)).unwrap();
+
+        let (left, right) = (workspace.region(0).unwrap(), workspace.region(1).unwrap());
+
+        assert_eq!(left.right(), right.left());
+        assert_eq!(right.right(), 999);
+        assert_eq!(left.size.h, 333);
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn move_workspace_scales_regions() {
+        let mut manager = Manager::default();
+        let small = manager.add_monitor(Rectangle::new(1920, 1080), Position::new(0, 0));
+        let large = manager.add_monitor(Rectangle::new(2560, 1440), Position::new(1920, 0));
+        let monitor = *manager.monitors.get(small).unwrap();
+        let index = manager.create_workspace(&monitor).unwrap();
+
+        manager
+            .workspaces
+            .get_mut(index)
+            .unwrap()
+            .create_region(0, &Direction::Right)
+            .unwrap();
+        manager.move_workspace(index, large).unwrap();
+
+        let workspace = manager.workspaces.get(index).unwrap();
+
+        assert_eq!(workspace.monitor, Some(large));
+        assert_eq!(workspace.size, Rectangle::new(2560, 1440));
+        assert_eq!(
+            workspace.region(0).unwrap().size,
+            Rectangle::new(1280, 1440)
+        );
+        assert_eq!(workspace.region(1).unwrap().pos, Position::new(1280, 0));
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+        assert_eq!(
+            manager.move_workspace(index, 2),
+            Err(ErrorKind::UnknownMonitor)
+        );
+        assert_eq!(
+            manager.move_workspace(1, large),
+            Err(ErrorKind::UnknownWorkspace)
+        );
+    }
+
+    #[test]
+    fn direction_helpers() {
+        for (direction, opposite, horizontal) in [
+            (Direction::Up, Direction::Down, false),
+            (Direction::Down, Direction::Up, false),
+            (Direction::Left, Direction::Right, true),
+            (Direction::Right, Direction::Left, true),
+        ] {
+            assert_eq!(direction.opposite(), opposite);
+            assert_eq!(direction.opposite().opposite(), direction);
+            assert_eq!(direction.is_horizontal(), horizontal);
+            assert_eq!(direction.is_vertical(), !horizontal);
+        }
+    }
+
+    #[test]
+    fn swap_region_updates_both_entries() {
+        let mut workspace = split_workspace();
+
+        workspace.create_region(1, &Direction::Down).unwrap();
+
+        let (left, right) = (*workspace.region(0).unwrap(), *workspace.region(1).unwrap());
+
+        workspace.swap_region(0, &Direction::Right).unwrap();
+
+        assert!(workspace.region(0).unwrap().same_geometry(&right));
+        assert!(workspace.region(1).unwrap().same_geometry(&left));
+        assert_eq!(
+            workspace.swap_region(0, &Direction::Right),
+            Err(ErrorKind::NoAdjacentRegions)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_json_round_trip() {
+        let mut manager = manager_with_workspaces(2);
+        let workspace = manager.workspaces.get_mut(1).unwrap();
+
+        workspace.history_depth = 4;
+        workspace.create_region(0, &Direction::Right).unwrap();
+        workspace.maximize_region(0, 0.75).unwrap();
+
+        let json = serde_json::to_string(&manager).unwrap();
+        let restored = serde_json::from_str::<Manager>(&json).unwrap();
+
+        assert!(!json.contains("undo_history"));
+        assert!(!json.contains("maximized"));
+        assert_eq!(restored.workspaces.len(), 2);
+        assert_eq!(
+            restored.workspaces[1].regions,
+            manager.workspaces[1].regions
+        );
+
+        let workspace = manager.workspaces.get_mut(1).unwrap();
+
+        workspace.undo_history.clear();
+        workspace.redo_history.clear();
+        workspace.maximized = None;
+
+        assert_eq!(restored, manager);
+    }
+
+    #[test]
+    fn tiled_and_floating_partition() {
+        let mut workspace = float_beside_split();
+
+        workspace.place_float(Rectangle::new(100, 100)).unwrap();
+
+        let tiled = workspace
+            .tiled_regions()
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        let floating = workspace
+            .floating_regions()
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+
+        assert_eq!(tiled, vec![0, 1]);
+        assert_eq!(floating, vec![2, 3]);
+
+        for (index, region) in workspace
+            .tiled_regions()
+            .chain(workspace.floating_regions())
+        {
+            assert_eq!(workspace.region(index).unwrap(), region);
+        }
+    }
+
+    #[test]
+    fn floats_are_not_adjacent() {
+        let workspace = float_beside_split();
+        let region = *workspace.region(0).unwrap();
+
+        assert_eq!(
+            workspace.adjacent_regions(&region, Direction::Right),
+            vec![1]
+        );
+        assert_eq!(
+            workspace.adjacent_regions(workspace.region(1).unwrap(), Direction::Left),
+            vec![0]
+        );
+    }
+
+    #[test]
+    fn resizing_tiled_leaves_floats() {
+        let mut workspace = float_beside_split();
+        let float = *workspace.region(2).unwrap();
+
+        workspace
+            .resize_region_by_index(0, &Resize::Right(-200))
+            .unwrap();
+
+        assert_eq!(workspace.region(1).unwrap().left(), 300);
+        assert_eq!(*workspace.region(2).unwrap(), float);
+    }
+
+    #[test]
+    fn resizing_float_leaves_tiled() {
+        let mut workspace = float_beside_split();
+        let tiled = workspace.regions[..2].to_vec();
+
+        workspace
+            .resize_region_by_index(2, &Resize::Right(50))
+            .unwrap();
+
+        assert_eq!(workspace.region(2).unwrap().size.w, 250);
+        assert_eq!(workspace.regions[..2], tiled);
+        assert!(workspace.layout_issues().is_empty());
+    }
+
+    #[test]
+    fn layout_rects_inner_gap() {
+        let mut workspace = split_workspace();
+
+        workspace.gaps = Gaps::new(10, 0);
+
+        let rects = workspace.layout_rects();
+
+        assert_eq!(rects[0].right(), 495);
+        assert_eq!(rects[1].left(), 505);
+        assert_eq!(rects[0].left(), 0);
+        assert_eq!(rects[1].right(), 1000);
+        assert_eq!(workspace.region(0).unwrap().right(), 500);
+    }
+
+    #[test]
+    fn layout_rects_outer_gap() {
+        let mut workspace = float_beside_split();
+
+        workspace.gaps = Gaps::new(0, 8);
+
+        let rects = workspace.layout_rects();
+
+        assert_eq!(rects[0].pos, Position::new(8, 8));
+        assert_eq!(rects[0].size, Rectangle::new(492, 484));
+        assert_eq!(rects[1].pos, Position::new(500, 8));
+        assert_eq!(rects[1].size, Rectangle::new(492, 484));
+        assert_eq!(rects[2], *workspace.region(2).unwrap());
+    }
+
+    #[test]
+    fn focus_neighbor_in_grid() {
+        let workspace = quad_workspace();
+
+        assert_eq!(workspace.focus_neighbor(0, &Direction::Right), Ok(1));
+        assert_eq!(workspace.focus_neighbor(0, &Direction::Down), Ok(2));
+        assert_eq!(workspace.focus_neighbor(3, &Direction::Up), Ok(1));
+        assert_eq!(
+            workspace.focus_neighbor(0, &Direction::Left),
+            Err(ErrorKind::NoAdjacentRegions)
+        );
+        assert_eq!(
+            workspace.focus_neighbor(4, &Direction::Left),
+            Err(ErrorKind::UnknownRegion)
+        );
+    }
+
+    #[test]
+    fn region_try_new_minimum() {
+        let pos = Position::new(0, 0);
+
+        assert_eq!(
+            Region::try_new(Rectangle::new(19, 20), pos, false),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(
+            Region::try_new(Rectangle::new(20, 19), pos, false),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(
+            Region::try_new(Rectangle::new(20, 20), pos, true),
+            Ok(Region::new(Rectangle::new(20, 20), pos, true))
+        );
+    }
+
+    #[test]
+    fn create_region_too_small() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 30));
+        let before = workspace.clone();
+
+        assert_eq!(
+            workspace.create_region(0, &Direction::Down),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn create_region_splits_tall_region() {
+        let mut workspace = Workspace::new(Rectangle::new(500, 1000));
+
+        assert_eq!(workspace.create_region(0, &Direction::Down), Ok(1));
+        assert_eq!(workspace.region(0).unwrap().size, Rectangle::new(500, 500));
+        assert_eq!(workspace.region(1).unwrap().pos, Position::new(0, 500));
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn split_region_quarter() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+        let index = workspace.split_region(0, &Direction::Right, 0.25).unwrap();
+
+        assert_eq!(workspace.region(0).unwrap().size.w, 750);
+        assert_eq!(workspace.region(index).unwrap().size.w, 250);
+        assert_eq!(workspace.region(index).unwrap().left(), 750);
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn split_region_below_minimum() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+        let before = workspace.clone();
+
+        for ratio in [0.01, 0.99, 0.0, 1.0] {
+            assert_eq!(
+                workspace.split_region(0, &Direction::Right, ratio),
+                Err(ErrorKind::InvalidRegion)
+            );
+        }
+
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn focus_survives_removing_other_region() {
+        let mut manager = manager_with_workspaces(1);
+
+        manager.workspaces.get_mut(0).unwrap().regions = quad_workspace().regions;
+        manager.focus(0, 3).unwrap();
+        manager
+            .workspaces
+            .get_mut(0)
+            .unwrap()
+            .remove_region(1)
+            .unwrap();
+
+        assert_eq!(manager.focused(), Some((0, 2)));
+
+        manager
+            .workspaces
+            .get_mut(0)
+            .unwrap()
+            .remove_region(2)
+            .unwrap();
+
+        assert_eq!(manager.focused(), Some((0, 0)));
+        assert_eq!(manager.focus(0, 5), Err(ErrorKind::UnknownRegion));
+        assert_eq!(manager.focus(1, 0), Err(ErrorKind::UnknownWorkspace));
+    }
+
+    #[test]
+    fn toggle_fullscreen_restores_geometry() {
+        let mut workspace = split_workspace();
+        let region = *workspace.region(1).unwrap();
+
+        assert_eq!(workspace.toggle_fullscreen(1), Ok(true));
+        assert_eq!(workspace.region(1).unwrap().size, Rectangle::new(1000, 500));
+        assert_eq!(workspace.region(1).unwrap().pos, Position::new(0, 0));
+        assert_eq!(workspace.toggle_fullscreen(1), Ok(false));
+        assert_eq!(*workspace.region(1).unwrap(), region);
+    }
+
+    #[test]
+    fn toggle_fullscreen_after_resize() {
+        let mut workspace = split_workspace();
+
+        workspace.toggle_fullscreen(1).unwrap();
+        workspace.resize(Rectangle::new(500, 250)).unwrap();
+
+        assert_eq!(workspace.region(1).unwrap().size, Rectangle::new(500, 250));
+        assert_eq!(workspace.toggle_fullscreen(1), Ok(false));
+
+        let region = workspace.region(1).unwrap();
+
+        assert_eq!(region.pos, Position::new(250, 0));
+        assert_eq!(region.size, Rectangle::new(250, 250));
+        assert_eq!(workspace.validate(), Ok(()));
+    }
+
+    #[test]
+    fn toggle_fullscreen_after_rotate() {
+        let mut workspace = split_workspace();
+
+        workspace.toggle_fullscreen(1).unwrap();
+        workspace.rotate_layout(Rectangle::new(500, 1000)).unwrap();
+
+        assert_eq!(workspace.region(1).unwrap().size, Rectangle::new(500, 1000));
+        assert_eq!(workspace.toggle_fullscreen(1), Ok(false));
+
+        let region = workspace.region(1).unwrap();
+
+        assert_eq!(region.pos, Position::new(0, 500));
+        assert_eq!(region.size, Rectangle::new(500, 500));
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn rectangle_scale_rounds_to_nearest() {
+        assert_eq!(Rectangle::new(10, 30).scale(0.33), Rectangle::new(3, 10));
+        assert_eq!(Rectangle::new(10, 30).scale(0.37), Rectangle::new(4, 11));
+        assert_eq!(Rectangle::new(1920, 1080).area(), 2_073_600);
+        assert_eq!(Rectangle::new(1920, 1080).aspect_ratio(), 16.0 / 9.0);
+    }
+
+    #[test]
+    fn position_negative_offsets() {
+        let pos = Position::new(10, 20);
+
+        assert_eq!(pos.offset(-30, -25), Position::new(-20, -5));
+        assert_eq!(pos + Position::new(-15, -40), Position::new(-5, -20));
+        assert_eq!(pos - Position::new(25, 21), Position::new(-15, -1));
+        assert_eq!(pos - Position::new(-5, -5), Position::new(15, 25));
+    }
+
+    #[test]
+    fn move_region_clamps_off_left_edge() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+        let float = workspace.place_float(Rectangle::new(200, 100)).unwrap();
+        let y = workspace.region(float).unwrap().top();
+
+        workspace
+            .move_region(float, Position::new(-2000, 0))
+            .unwrap();
+
+        assert_eq!(workspace.region(float).unwrap().pos, Position::new(0, y));
+
+        workspace.float_clamp = FloatClamp::Partial(50);
+        workspace
+            .move_region(float, Position::new(-2000, 0))
+            .unwrap();
+
+        assert_eq!(workspace.region(float).unwrap().pos, Position::new(-150, y));
+        assert_eq!(
+            workspace.move_region(0, Position::new(10, 0)),
+            Err(ErrorKind::InvalidRegion)
+        );
+    }
+
+    #[test]
+    fn area_saturates() {
+        let near = u32::MAX as u64 + 1;
+        let region = Region::new(Rectangle::new(near, near), Position::new(0, 0), false);
+
+        assert_eq!(region.area(), u64::MAX);
+        assert_eq!(
+            Rectangle::new(u32::MAX as u64, u32::MAX as u64).area(),
+            (u32::MAX as u64) * (u32::MAX as u64)
+        );
+    }
+
+    #[test]
+    fn validate_rejects_overlap() {
+        let mut workspace = split_workspace();
+
+        assert_eq!(workspace.validate(), Ok(()));
+
+        workspace.regions.get_mut(1).unwrap().pos.x = 400;
+
+        assert_eq!(workspace.validate(), Err(ErrorKind::InvalidRegion));
+    }
+
+    #[test]
+    fn error_kind_display() {
+        for (error, message) in [
+            (ErrorKind::UnknownRegion, "unknown region"),
+            (ErrorKind::UnknownWorkspace, "unknown workspace"),
+            (ErrorKind::UnknownMonitor, "unknown monitor"),
+            (ErrorKind::InvalidRegion, "invalid region geometry"),
+            (ErrorKind::NoAdjacentRegions, "no adjacent regions"),
+            (ErrorKind::NoHistory, "no history to restore"),
+            (ErrorKind::InvalidMonitor, "invalid monitor"),
+        ] {
+            assert_eq!(error.to_string(), message);
+        }
+    }
+
+    #[test]
+    fn grow_pushes_neighbor() {
+        let mut workspace = split_workspace();
+
+        workspace.grow(0, &Direction::Right, 50).unwrap();
+
+        assert_eq!(workspace.region(0).unwrap().size.w, 550);
+        assert_eq!(workspace.region(1).unwrap().size.w, 450);
+        assert_eq!(workspace.region(1).unwrap().left(), 550);
+
+        workspace.grow(1, &Direction::Left, -100).unwrap();
+
+        assert_eq!(workspace.region(0).unwrap().size.w, 650);
+        assert_eq!(workspace.region(1).unwrap().size.w, 350);
+    }
+
+    #[test]
+    fn resize_rolls_back_with_unequal_siblings() {
+        let mut workspace = split_workspace();
+
+        workspace.create_region(1, &Direction::Down).unwrap();
+        workspace.create_region(2, &Direction::Right).unwrap();
+
+        assert_eq!(
+            workspace.adjacent_regions(workspace.region(0).unwrap(), Direction::Right),
+            vec![1, 2]
+        );
+
+        let before = workspace.clone();
+
+        assert_eq!(
+            workspace.resize_region_by_index(0, &Resize::Right(240)),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(workspace, before);
+
+        let mut region = *workspace.region(0).unwrap();
+
+        assert_eq!(
+            workspace.resize_region(&mut region, Resize::Right(240)),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(region, *before.region(0).unwrap());
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn equalize_three_splits() {
+        let mut workspace = Workspace::new(Rectangle::new(900, 600));
+
+        workspace.create_region(0, &Direction::Right).unwrap();
+        workspace.create_region(1, &Direction::Right).unwrap();
+        workspace.equalize(&Direction::Right).unwrap();
+
+        for (index, left) in [(0, 0), (1, 300), (2, 600)] {
+            let region = workspace.region(index).unwrap();
+
+            assert_eq!(region.left(), left);
+            assert_eq!(region.size, Rectangle::new(300, 600));
+        }
+
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn equalize_balances_each_stack() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.create_region(0, &Direction::Right).unwrap();
+        workspace.split_region(0, &Direction::Down, 0.8).unwrap();
+        workspace.split_region(1, &Direction::Down, 0.2).unwrap();
+        workspace.equalize(&Direction::Down).unwrap();
+
+        for index in 0..4 {
+            assert_eq!(workspace.region(index).unwrap().size.h, 250);
+        }
+
+        assert_eq!(workspace.region(2).unwrap().top(), 250);
+        assert_eq!(workspace.region(3).unwrap().top(), 250);
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn bsp_layout_counts() {
+        for count in [1, 2, 4, 5] {
+            let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+            let indices = workspace.bsp_layout(count).unwrap();
+
+            assert_eq!(indices.len(), count);
+            assert_eq!(workspace.region_count(), count);
+            assert_eq!(workspace.validate(), Ok(()));
+            assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+        }
+
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        assert_eq!(workspace.bsp_layout(0), Err(ErrorKind::InvalidRegion));
+    }
+
+    #[test]
+    fn master_stack_three() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 600));
+        let indices = workspace.master_stack(3, 0.6).unwrap();
+
+        assert_eq!(indices, vec![0, 1, 2]);
+        assert_eq!(workspace.region(0).unwrap().size, Rectangle::new(600, 600));
+
+        for (index, y) in [(1, 0), (2, 300)] {
+            let region = workspace.region(index).unwrap();
+
+            assert_eq!(region.size, Rectangle::new(400, 300));
+            assert_eq!(region.pos, Position::new(600, y));
+        }
+
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn closest_region_in_grid() {
+        let workspace = quad_workspace();
+
+        assert_eq!(
+            workspace.closest_region_to(Position::new(-50, -50)),
+            Some(0)
+        );
+        assert_eq!(
+            workspace.closest_region_to(Position::new(900, 100)),
+            Some(1)
+        );
+        assert_eq!(
+            workspace.closest_region_to(Position::new(200, 450)),
+            Some(2)
+        );
+        assert_eq!(
+            workspace.closest_region_to(Position::new(1200, 600)),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn closest_region_to_extreme_positions() {
+        let workspace = quad_workspace();
+
+        assert_eq!(
+            workspace.closest_region_to(Position::new(i64::MIN, i64::MIN)),
+            Some(0)
+        );
+        assert_eq!(
+            workspace.closest_region_to(Position::new(i64::MAX, i64::MAX)),
+            Some(3)
+        );
+        assert_eq!(
+            workspace.closest_region_to(Position::new(i64::MAX, i64::MIN)),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn closest_region_tie_prefers_lowest_index() {
+        let workspace = quad_workspace();
+
+        assert_eq!(
+            workspace.closest_region_to(Position::new(500, 250)),
+            Some(0)
+        );
+        assert_eq!(
+            workspace.closest_region_to(Position::new(500, 400)),
+            Some(2)
+        );
+        assert_eq!(
+            Workspace::empty(Rectangle::new(1000, 500)).closest_region_to(Position::new(0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn monitor_at_side_by_side() {
+        let mut manager = Manager::default();
+
+        manager.add_monitor(Rectangle::new(1920, 1080), Position::new(0, 0));
+        manager.add_monitor(Rectangle::new(1280, 1024), Position::new(2000, 0));
+
+        assert_eq!(manager.monitor_at(Position::new(0, 0)), Some(0));
+        assert_eq!(manager.monitor_at(Position::new(1919, 1079)), Some(0));
+        assert_eq!(manager.monitor_at(Position::new(2000, 500)), Some(1));
+        assert_eq!(manager.monitor_at(Position::new(1950, 500)), None);
+        assert_eq!(manager.monitor_at(Position::new(2100, 1050)), None);
+    }
+
+    #[test]
+    fn region_global_pos_offsets_by_monitor() {
+        let mut manager = Manager::default();
+
+        manager.add_monitor(Rectangle::new(1920, 1080), Position::new(0, 0));
+
+        let second = manager.add_monitor(Rectangle::new(1920, 1080), Position::new(1920, 0));
+        let monitor = *manager.monitors.get(second).unwrap();
+        let index = manager.create_workspace(&monitor).unwrap();
+
+        manager
+            .workspaces
+            .get_mut(index)
+            .unwrap()
+            .create_region(0, &Direction::Right)
+            .unwrap();
+
+        assert_eq!(
+            manager.region_global_pos(index, 0),
+            Ok(Position::new(1920, 0))
+        );
+        assert_eq!(
+            manager.region_global_pos(index, 1),
+            Ok(Position::new(2880, 0))
+        );
+
+        manager.workspaces.get_mut(index).unwrap().monitor = None;
+
+        assert_eq!(
+            manager.region_global_pos(index, 0),
+            Err(ErrorKind::UnknownMonitor)
+        );
+    }
+
+    #[test]
+    fn merge_workspaces_adds_floats() {
+        let mut manager = manager_with_workspaces(2);
+        let (source, target) = (manager.workspaces[0].id, manager.workspaces[1].id);
+
+        manager.workspaces.get_mut(0).unwrap().regions = split_workspace().regions;
+        manager
+            .workspaces
+            .get_mut(0)
+            .unwrap()
+            .group_as_tabs(&[0, 1])
+            .unwrap();
+        manager
+            .workspaces
+            .get_mut(1)
+            .unwrap()
+            .group_as_tabs(&[0])
+            .unwrap();
+        manager.merge_workspaces(0, 1).unwrap();
+
+        assert_eq!(manager.workspaces.len(), 1);
+        assert_eq!(
+            manager.workspace(source).map(|_| ()),
+            Err(ErrorKind::UnknownWorkspace)
+        );
+
+        let workspace = manager.workspace(target).unwrap();
+
+        assert_eq!(workspace.region_count(), 3);
+        assert_eq!(workspace.count_floating(), 2);
+        assert!(workspace
+            .floating_regions()
+            .all(|(_, region)| region.tab_group.is_none()));
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn merge_workspaces_restacks_and_records() {
+        let mut manager = manager_with_workspaces(2);
+        let source = manager.workspaces.get_mut(0).unwrap();
+
+        source.regions = stacked_floats().regions;
+        source.lower_region(3).unwrap();
+
+        let target = manager.workspaces.get_mut(1).unwrap();
+
+        target.history_depth = 5;
+        target.place_float(Rectangle::new(200, 200)).unwrap();
+        target.place_float(Rectangle::new(200, 200)).unwrap();
+        manager.merge_workspaces(0, 1).unwrap();
+
+        let workspace = manager.workspaces.get_mut(0).unwrap();
+        let mut stacking = workspace
+            .floating_regions()
+            .map(|(index, region)| (region.z, index))
+            .collect::<Vec<_>>();
+
+        stacking.sort();
+
+        assert_eq!(
+            stacking
+                .into_iter()
+                .map(|(_, index)| index)
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3, 6, 4, 5]
+        );
+
+        workspace.undo().unwrap();
+
+        assert_eq!(workspace.region_count(), 3);
+    }
+
+    #[test]
+    fn direction_all_once() {
+        let all = Direction::all();
+
+        for direction in [
+            Direction::Up,
+            Direction::Down,
+            Direction::Left,
+            Direction::Right,
+        ] {
+            assert_eq!(all.iter().filter(|other| **other == direction).count(), 1);
+        }
+    }
+
+    #[test]
+    fn region_accessors() {
+        let mut workspace = split_workspace();
+
+        assert_eq!(workspace.region(1).unwrap().left(), 500);
+        assert_eq!(workspace.region(2), Err(ErrorKind::UnknownRegion));
+
+        workspace.region_mut(1).unwrap().float = true;
+
+        assert!(workspace.regions[1].float);
+        assert_eq!(
+            workspace.region_mut(2).map(|_| ()),
+            Err(ErrorKind::UnknownRegion)
+        );
+    }
+
+    #[test]
+    fn round_bias_odd_height() {
+        for (bias, sibling, new) in [(RoundBias::Sibling, 51, 50), (RoundBias::New, 50, 51)] {
+            let mut workspace = Workspace::new(Rectangle::new(100, 101));
+
+            workspace.round_bias = bias;
+
+            let index = workspace.create_region(0, &Direction::Down).unwrap();
+
+            assert_eq!(workspace.region(0).unwrap().size.h, sibling);
+            assert_eq!(workspace.region(index).unwrap().size.h, new);
+            assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+        }
+    }
+
+    #[test]
+    fn cycle_regions_forward() {
+        let mut workspace = float_beside_split();
+
+        workspace.create_region(1, &Direction::Right).unwrap();
+
+        let before = workspace.clone();
+
+        workspace.cycle_regions(false).unwrap();
+
+        for (index, from) in [(0, 3), (1, 0), (3, 1)] {
+            assert!(workspace.regions[index].same_geometry(&before.regions[from]));
+        }
+
+        assert_eq!(workspace.regions[2], before.regions[2]);
+    }
+
+    #[test]
+    fn cycle_regions_reverse() {
+        let mut workspace = three_columns();
+        let before = workspace.clone();
+
+        workspace.cycle_regions(true).unwrap();
+
+        for (index, from) in [(0, 1), (1, 2), (2, 0)] {
+            assert!(workspace.regions[index].same_geometry(&before.regions[from]));
+        }
+
+        workspace.cycle_regions(false).unwrap();
+
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn major_adjacent_region_tie_break() {
+        let mut workspace = split_workspace();
+
+        workspace.create_region(1, &Direction::Down).unwrap();
+
+        let region = *workspace.region(0).unwrap();
+
+        for _ in 0..3 {
+            assert_eq!(
+                workspace.major_adjacent_region(&region, Direction::Right),
+                Some(1)
+            );
+        }
+
+        workspace.regions.swap(1, 2);
+
+        assert_eq!(
+            workspace.major_adjacent_region(&region, Direction::Right),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn regions_in_direction_column() {
+        let mut workspace = Workspace::new(Rectangle::new(500, 900));
+
+        workspace.grid_layout(3, 1).unwrap();
+        workspace.regions.reverse();
+
+        assert_eq!(
+            workspace.regions_in_direction(2, &Direction::Down),
+            vec![1, 0]
+        );
+        assert_eq!(
+            workspace.regions_in_direction(0, &Direction::Up),
+            vec![1, 2]
+        );
+        assert!(workspace.regions_in_direction(2, &Direction::Up).is_empty());
+        assert!(workspace
+            .regions_in_direction(3, &Direction::Down)
+            .is_empty());
+    }
+
+    #[test]
+    fn resize_region_clamped_diagonal_undoes_once() {
+        let mut workspace = quad_workspace();
+        let quad = workspace.regions.clone();
+
+        workspace.history_depth = 5;
+
+        assert_eq!(
+            workspace.resize_region_clamped(0, &Resize::BottomRight(50, 50)),
+            Resize::BottomRight(50, 50)
+        );
+
+        workspace.undo().unwrap();
+
+        assert_eq!(workspace.regions, quad);
+        assert_eq!(workspace.undo(), Err(ErrorKind::NoHistory));
+    }
+
+    #[test]
+    fn resize_region_clamped_respects_tab_followers() {
+        let mut workspace = three_columns();
+
+        workspace.group_as_tabs(&[0, 2]).unwrap();
+        workspace.regions.get_mut(2).unwrap().max = Some(Rectangle::new(350, 600));
+
+        assert_eq!(
+            workspace.resize_region_clamped(2, &Resize::Right(200)),
+            Resize::Right(50)
+        );
+        assert_eq!(workspace.region(0).unwrap().size.w, 350);
+        assert_eq!(workspace.region(2).unwrap().size.w, 350);
+        assert_eq!(
+            workspace.resize_region_clamped(0, &Resize::Right(200)),
+            Resize::Right(0)
+        );
+    }
+
+    #[test]
+    fn resize_region_clamped_to_available_space() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+        let index = workspace.split_region(0, &Direction::Right, 0.05).unwrap();
+
+        assert_eq!(workspace.region(index).unwrap().size.w, 50);
+        assert_eq!(
+            workspace.resize_region_clamped(0, &Resize::Right(100)),
+            Resize::Right(30)
+        );
+        assert_eq!(workspace.region(0).unwrap().size.w, 980);
+        assert_eq!(workspace.region(index).unwrap().size.w, 20);
+        assert_eq!(
+            workspace.resize_region_clamped(0, &Resize::TopRight(-10, 10)),
+            Resize::TopRight(0, 0)
+        );
+        assert_eq!(
+            workspace.resize_region_clamped(2, &Resize::Left(10)),
+            Resize::Left(0)
+        );
+    }
+
+    #[test]
+    fn tuple_round_trips() {
+        let rectangle = Rectangle::from((1920, 1080));
+        let position = Position::from((-20, 40));
+
+        assert_eq!(rectangle, Rectangle::new(1920, 1080));
+        assert_eq!(position, Position::new(-20, 40));
+        assert_eq!(<(u64, u64)>::from(rectangle), (1920, 1080));
+        assert_eq!(<(i64, i64)>::from(position), (-20, 40));
+
+        let (w, h): (u64, u64) = Rectangle::new(3, 4).into();
+        let pos: Position = (5, -6).into();
+
+        assert_eq!((w, h), (3, 4));
+        assert_eq!(pos, Position::new(5, -6));
+    }
+
+    #[test]
+    fn snap_to_grid_of_eight() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.split_region(0, &Direction::Right, 0.333).unwrap();
+        workspace.split_region(1, &Direction::Down, 0.61).unwrap();
+        workspace.snap_to_grid(8).unwrap();
+
+        for (_, region) in workspace.tiled_regions() {
+            for direction in Direction::all() {
+                let edge = region.edge(direction);
+
+                assert!(edge % 8 == 0 || edge == 1000 || edge == 500);
+            }
+        }
+
+        assert_eq!(workspace.region(0).unwrap().right(), 664);
+        assert_eq!(workspace.region(1).unwrap().left(), 664);
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn layout_issues_overlap_and_out_of_bounds() {
+        let mut workspace = three_columns();
+
+        workspace.regions.get_mut(1).unwrap().pos.x = 200;
+        workspace.regions.get_mut(2).unwrap().pos.x = 700;
+
+        let issues = workspace.layout_issues();
+
+        assert!(issues.contains(&LayoutIssue::OutOfBounds(2)));
+        assert!(issues.contains(&LayoutIssue::Overlap(0, 1)));
+        assert!(issues.contains(&LayoutIssue::Gap(
+            Rectangle::new(200, 600),
+            Position::new(500, 0)
+        )));
+        assert_eq!(issues.len(), 3);
+    }
+
+    #[test]
+    fn workspaces_on_monitor_grouping() {
+        let mut manager = Manager::default();
+
+        for x in [0, 1000, 2000] {
+            manager.add_monitor(Rectangle::new(1000, 500), Position::new(x, 0));
+        }
+
+        for monitor in [0, 1, 0] {
+            let monitor = *manager.monitors.get(monitor).unwrap();
+
+            manager.create_workspace(&monitor).unwrap();
+        }
+
+        assert_eq!(
+            manager.workspaces_on_monitor(0).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(
+            manager.workspaces_on_monitor(1).collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert_eq!(manager.workspaces_on_monitor(2).count(), 0);
+    }
+
+    #[test]
+    fn resize_then_invert_restores_layout() {
+        let mut workspace = split_workspace();
+
+        workspace.create_region(0, &Direction::Down).unwrap();
+
+        let before = workspace.clone();
+        let resize = Resize::BottomRight(30, 40);
+
+        assert_eq!(resize.invert(), Resize::BottomRight(-30, -40));
+
+        workspace.resize_region_by_index(0, &resize).unwrap();
+
+        assert_ne!(workspace, before);
+
+        workspace
+            .resize_region_by_index(0, &resize.invert())
+            .unwrap();
+
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn undo_and_redo_steps() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.history_depth = 8;
+
+        let initial = workspace.regions.clone();
+
+        workspace.create_region(0, &Direction::Right).unwrap();
+
+        let created = workspace.regions.clone();
+
+        workspace.grow(0, &Direction::Right, 100).unwrap();
+
+        let resized = workspace.regions.clone();
+
+        workspace.undo().unwrap();
+
+        assert_eq!(workspace.regions, created);
+
+        workspace.undo().unwrap();
+
+        assert_eq!(workspace.regions, initial);
+        assert_eq!(workspace.undo(), Err(ErrorKind::NoHistory));
+
+        workspace.redo().unwrap();
+
+        assert_eq!(workspace.regions, created);
+
+        workspace.redo().unwrap();
+
+        assert_eq!(workspace.regions, resized);
+        assert_eq!(workspace.redo(), Err(ErrorKind::NoHistory));
+    }
+
+    #[test]
+    fn find_largest_region() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.split_region(0, &Direction::Right, 0.6).unwrap();
+        workspace.split_region(0, &Direction::Down, 0.5).unwrap();
+
+        let largest = workspace.regions.iter().map(Region::area).max().unwrap();
+
+        assert_eq!(
+            workspace.find_region(|region| region.area() == largest),
+            Some(1)
+        );
+        assert_eq!(
+            workspace.find_region(|region| region.area() > largest),
+            None
+        );
+    }
+
+    #[test]
+    fn find_floating_regions() {
+        let mut workspace = float_beside_split();
+
+        workspace.place_float(Rectangle::new(100, 100)).unwrap();
+
+        assert_eq!(workspace.find_regions(|region| region.float), vec![2, 3]);
+        assert_eq!(workspace.find_regions(|region| !region.float), vec![0, 1]);
+        assert_eq!(workspace.find_region(|region| region.float), Some(2));
+    }
+
+    #[test]
+    fn direction_axis_mapping() {
+        assert_eq!(Direction::Up.axis(), Axis::Vertical);
+        assert_eq!(Direction::Down.axis(), Axis::Vertical);
+        assert_eq!(Direction::Left.axis(), Axis::Horizontal);
+        assert_eq!(Direction::Right.axis(), Axis::Horizontal);
+        assert_eq!(Axis::Horizontal.perpendicular(), Axis::Vertical);
+        assert_eq!(Axis::Vertical.perpendicular(), Axis::Horizontal);
+    }
+
+    #[test]
+    fn adjacency_in_grid_unchanged() {
+        let workspace = quad_workspace();
+        let adjacent = |index: usize, direction| {
+            workspace.adjacent_regions(workspace.region(index).unwrap(), direction)
+        };
+
+        assert_eq!(adjacent(0, Direction::Right), vec![1]);
+        assert_eq!(adjacent(0, Direction::Down), vec![2]);
+        assert_eq!(adjacent(3, Direction::Up), vec![1]);
+        assert_eq!(adjacent(3, Direction::Left), vec![2]);
+        assert!(adjacent(0, Direction::Up).is_empty());
+        assert!(adjacent(3, Direction::Right).is_empty());
+    }
+
+    #[test]
+    fn region_off_right_edge_is_not_within() {
+        let workspace = Workspace::new(Rectangle::new(1000, 500));
+        let inside = Region::new(Rectangle::new(200, 100), Position::new(800, 400), true);
+
+        assert_eq!(workspace.bounds(), workspace.regions[0]);
+        assert!(workspace.is_within(&inside));
+        assert!(!workspace.is_within(&inside.translated(1, 0)));
+    }
+
+    #[test]
+    fn empty_workspace_has_no_regions() {
+        let workspace = Workspace::empty(Rectangle::new(1000, 500));
+
+        assert_eq!(workspace.region_count(), 0);
+        assert_eq!(workspace.validate(), Ok(()));
+        assert_eq!(
+            workspace.assert_tiles_exactly(),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(
+            workspace.layout_issues(),
+            vec![LayoutIssue::Gap(
+                Rectangle::new(1000, 500),
+                Position::new(0, 0)
+            )]
+        );
+        assert_eq!(workspace.uncovered_areas(), vec![workspace.bounds()]);
+    }
+
+    #[test]
+    fn resize_region_to_absolute_bottom() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 1000));
+
+        workspace.create_region(0, &Direction::Down).unwrap();
+        workspace.resize_region_to(0, Direction::Down, 800).unwrap();
+
+        assert_eq!(workspace.region(0).unwrap().bottom(), 800);
+        assert_eq!(workspace.region(1).unwrap().top(), 800);
+        assert_eq!(workspace.region(1).unwrap().size.h, 200);
+        assert_eq!(
+            workspace.resize_region_to(0, Direction::Down, 990),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(
+            workspace.resize_region_to(2, Direction::Down, 900),
+            Err(ErrorKind::UnknownRegion)
+        );
+    }
+
+    #[test]
+    fn total_bounds_with_negative_positions() {
+        let mut manager = Manager::default();
+
+        assert_eq!(manager.total_bounds(), None);
+
+        manager.add_monitor(Rectangle::new(1920, 1080), Position::new(0, 0));
+        manager.add_monitor(Rectangle::new(1920, 1080), Position::new(1920, -200));
+
+        let bounds = manager.total_bounds().unwrap();
+
+        assert_eq!(bounds.pos, Position::new(0, -200));
+        assert_eq!(bounds.size, Rectangle::new(3840, 1280));
+    }
+
+    #[test]
+    fn with_edges_leave_original() {
+        let region = Region::new(Rectangle::new(100, 100), Position::new(0, 0), false);
+
+        assert_eq!(region.with_top(30).unwrap().pos, Position::new(0, 30));
+        assert_eq!(region.with_top(30).unwrap().size, Rectangle::new(100, 70));
+        assert_eq!(region.with_bottom(50).unwrap().size.h, 50);
+        assert_eq!(region.with_left(-20).unwrap().size.w, 120);
+        assert_eq!(region.with_right(90).unwrap().right(), 90);
+        assert_eq!(region.with_right(10), Err(ErrorKind::InvalidRegion));
+        assert_eq!(
+            region,
+            Region::new(Rectangle::new(100, 100), Position::new(0, 0), false)
+        );
+    }
+
+    #[test]
+    fn regions_sorted_reading_order() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.create_region(0, &Direction::Down).unwrap();
+        workspace.create_region(1, &Direction::Right).unwrap();
+        workspace.create_region(0, &Direction::Right).unwrap();
+
+        assert_eq!(workspace.regions_sorted(), vec![0, 3, 1, 2]);
+    }
+
+    #[test]
+    fn region_count_and_is_empty() {
+        let mut workspace = Workspace::empty(Rectangle::new(1000, 500));
+
+        assert_eq!(workspace.region_count(), 0);
+        assert!(workspace.is_empty());
+
+        workspace.regions.extend([Region::new(
+            Rectangle::new(200, 200),
+            Position::new(100, 100),
+            true,
+        )]);
+
+        assert_eq!(workspace.region_count(), 1);
+        assert!(workspace.is_empty());
+
+        let workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        assert_eq!(workspace.region_count(), 1);
+        assert!(!workspace.is_empty());
+    }
+
+    #[test]
+    fn insert_region_placement() {
+        for (direction, new, old) in [
+            (Direction::Up, Position::new(0, 0), Position::new(0, 250)),
+            (Direction::Down, Position::new(0, 250), Position::new(0, 0)),
+            (Direction::Left, Position::new(0, 0), Position::new(500, 0)),
+            (Direction::Right, Position::new(500, 0), Position::new(0, 0)),
+        ] {
+            let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+            assert_eq!(workspace.insert_region(0, &direction), Ok(1));
+            assert_eq!(workspace.focused_region, Some(1));
+            assert_eq!(workspace.region(1).unwrap().pos, new);
+            assert_eq!(workspace.region(0).unwrap().pos, old);
+            assert!(workspace.assert_tiles_exactly().is_ok());
+        }
+    }
+
+    #[test]
+    fn resize_region_by_index_keeps_regions_consistent() {
+        let mut workspace = split_workspace();
+
+        workspace
+            .resize_region_by_index(0, &Resize::Right(100))
+            .unwrap();
+
+        assert_eq!(workspace.region(0).unwrap().size.w, 600);
+        assert_eq!(workspace.region(1).unwrap().left(), 600);
+        assert_eq!(workspace.region(1).unwrap().size.w, 400);
+        assert!(workspace.assert_tiles_exactly().is_ok());
+        assert_eq!(
+            workspace.resize_region_by_index(0, &Resize::Right(1000)),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(workspace.region(0).unwrap().size.w, 600);
+        assert!(workspace.assert_tiles_exactly().is_ok());
+    }
+
+    #[test]
+    fn describe_matches_regions() {
+        let workspace = float_beside_split();
+        let infos = workspace.describe();
+
+        assert_eq!(infos.len(), workspace.region_count());
+
+        for (info, (index, region)) in infos.iter().zip(workspace.regions.iter().enumerate()) {
+            assert_eq!(info.index, index);
+            assert_eq!(info.rect, region.size);
+            assert_eq!(info.pos, region.pos);
+            assert_eq!(info.float, region.float);
+        }
+    }
+
+    #[test]
+    fn remove_region_restores_on_max_limit() {
+        let mut workspace = split_workspace();
+
+        workspace.focused_region = Some(1);
+        workspace.regions.get_mut(0).unwrap().max = Some(Rectangle::new(600, 500));
+
+        let before = workspace.clone();
+
+        assert_eq!(workspace.remove_region(1), Err(ErrorKind::InvalidRegion));
+        assert_eq!(workspace, before);
+        assert_eq!(workspace.float_region(1), Err(ErrorKind::InvalidRegion));
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn manager_move_region_restores_on_max_limit() {
+        let mut manager = manager_with_workspaces(2);
+
+        manager.workspaces.get_mut(0).unwrap().regions = split_workspace().regions;
+        manager.workspaces.get_mut(0).unwrap().regions[0].max = Some(Rectangle::new(600, 500));
+
+        let before = manager.clone();
+
+        assert_eq!(manager.move_region(0, 1, 1), Err(ErrorKind::InvalidRegion));
+        assert_eq!(manager, before);
+    }
+
+    #[test]
+    fn resize_region_refuses_to_grow_past_max() {
+        let mut workspace = split_workspace();
+
+        workspace.regions.get_mut(0).unwrap().max = Some(Rectangle::new(550, 500));
+
+        let before = workspace.clone();
+        let mut region = *workspace.region(0).unwrap();
+
+        assert_eq!(
+            workspace.resize_region(&mut region, Resize::Right(100)),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(region, *before.region(0).unwrap());
+        assert_eq!(
+            workspace.resize_region_by_index(0, &Resize::Right(100)),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn resize_region_refuses_to_shrink_past_min() {
+        let mut workspace = split_workspace();
+
+        workspace.regions.get_mut(1).unwrap().min = Some(Rectangle::new(450, 100));
+
+        let before = workspace.clone();
+        let mut region = *workspace.region(1).unwrap();
+
+        assert_eq!(
+            workspace.resize_region(&mut region, Resize::Left(100)),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(region, *before.region(1).unwrap());
+        assert_eq!(
+            workspace.resize_region_by_index(0, &Resize::Right(100)),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn resize_region_clamped_respects_limits() {
+        let mut workspace = split_workspace();
+
+        workspace.regions.get_mut(0).unwrap().max = Some(Rectangle::new(600, 500));
+
+        assert_eq!(
+            workspace.resize_region_clamped(0, &Resize::Right(200)),
+            Resize::Right(100)
+        );
+        assert_eq!(workspace.region(0).unwrap().size.w, 600);
+        assert_eq!(workspace.region(1).unwrap().left(), 600);
+
+        workspace.regions.get_mut(1).unwrap().max = Some(Rectangle::new(450, 500));
+
+        assert_eq!(
+            workspace.resize_region_clamped(0, &Resize::Right(-200)),
+            Resize::Right(-50)
+        );
+        assert_eq!(workspace.region(1).unwrap().size.w, 450);
+
+        workspace.regions.get_mut(1).unwrap().max = None;
+        workspace.regions.get_mut(0).unwrap().min = Some(Rectangle::new(500, 20));
+
+        assert_eq!(
+            workspace.resize_region_clamped(0, &Resize::Right(-100)),
+            Resize::Right(-50)
+        );
+        assert_eq!(workspace.region(0).unwrap().size.w, 500);
+    }
+
+    #[test]
+    fn swap_regions_across_grid() {
+        let mut workspace = quad_workspace();
+        let (top_left, bottom_right) =
+            (*workspace.region(0).unwrap(), *workspace.region(3).unwrap());
+
+        workspace.swap_regions(0, 3).unwrap();
+
+        assert!(workspace.region(0).unwrap().same_geometry(&bottom_right));
+        assert!(workspace.region(3).unwrap().same_geometry(&top_left));
+        assert!(workspace.assert_tiles_exactly().is_ok());
+        assert_eq!(workspace.swap_regions(0, 4), Err(ErrorKind::UnknownRegion));
+        assert_eq!(workspace.swap_regions(4, 0), Err(ErrorKind::UnknownRegion));
+    }
+
+    #[test]
+    fn grid_layout_two_by_three() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        assert_eq!(workspace.grid_layout(2, 3), Ok(vec![0, 1, 2, 3, 4, 5]));
+        assert_eq!(
+            workspace
+                .regions
+                .iter()
+                .map(|region| (region.pos, region.size))
+                .collect::<Vec<_>>(),
+            vec![
+                (Position::new(0, 0), Rectangle::new(334, 250)),
+                (Position::new(334, 0), Rectangle::new(333, 250)),
+                (Position::new(667, 0), Rectangle::new(333, 250)),
+                (Position::new(0, 250), Rectangle::new(334, 250)),
+                (Position::new(334, 250), Rectangle::new(333, 250)),
+                (Position::new(667, 250), Rectangle::new(333, 250)),
+            ]
+        );
+        assert!(workspace.validate().is_ok());
+        assert!(workspace.assert_tiles_exactly().is_ok());
+        assert_eq!(workspace.grid_layout(0, 3), Err(ErrorKind::InvalidRegion));
+    }
+
+    #[test]
+    fn summary_counts() {
+        let mut manager = manager_with_workspaces(2);
+
+        manager
+            .workspaces
+            .get_mut(0)
+            .unwrap()
+            .create_region(0, &Direction::Right)
+            .unwrap();
+
+        let summary = manager.summary();
+
+        assert!(summary.contains("monitors: 1\n"));
+        assert!(summary.contains("workspaces: 2\n"));
+        assert!(summary.contains("  0 (id 1) on monitor 0: 1000x500, regions: 2\n"));
+        assert!(summary.contains("  1 (id 2) on monitor 0: 1000x500, regions: 1\n"));
+        assert!(summary.contains("    1: 500x500 at (500, 0)\n"));
+    }
+
+    #[test]
+    fn translate_into_negative_coordinates() {
+        let mut region = Region::new(Rectangle::new(100, 50), Position::new(10, 20), true);
+        let moved = region.translated(-60, -70);
+
+        assert_eq!(moved.pos, Position::new(-50, -50));
+        assert_eq!(moved.size, Rectangle::new(100, 50));
+        assert_eq!(region.pos, Position::new(10, 20));
+
+        region.translate(-60, -70);
+
+        assert_eq!(region, moved);
+        assert_eq!(region.right(), 50);
+    }
 
-// impl Monitor {
-//     pub fn new<I>(size: Rectangle, pos: Position) -> Self {
-//         Self { size, pos }
-//     }
-// }
+    #[test]
+    fn place_float_cascades() {
+        let workspace = stacked_floats();
+        let positions = workspace
+            .floating_regions()
+            .map(|(_, region)| region.pos)
+            .collect::<Vec<_>>();
 
-// impl Manager {
-//     pub fn create_workspace(&mut self, monitor: &Monitor) -> Result<&Workspace> {
-//         if !self.monitors.contains(monitor) {
-//             return Err(ErrorKind::UnknownMonitor);
-//         }
+        assert_eq!(
+            positions,
+            vec![
+                Position::new(400, 150),
+                Position::new(432, 182),
+                Position::new(464, 214),
+            ]
+        );
+        assert!(workspace
+            .floating_regions()
+            .all(|(_, region)| workspace.is_within(region)));
+    }
+
+    #[test]
+    fn maximize_middle_of_three() {
+        let mut workspace = Workspace::new(Rectangle::new(900, 600));
+
+        workspace.grid_layout(1, 3).unwrap();
+
+        let columns = workspace.regions.clone();
+
+        workspace.maximize_region(1, 0.5).unwrap();
+
+        assert_eq!(workspace.region(1).unwrap().size, Rectangle::new(450, 600));
+        assert_eq!(workspace.region(1).unwrap().pos, Position::new(0, 0));
+
+        for (index, y) in [(0, 0), (2, 300)] {
+            let region = workspace.region(index).unwrap();
+
+            assert_eq!(region.size, Rectangle::new(450, 300));
+            assert_eq!(region.pos, Position::new(450, y));
+        }
+
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+
+        workspace.unmaximize().unwrap();
+
+        assert_eq!(workspace.regions, columns);
+    }
+
+    #[test]
+    fn undo_maximize_discards_kept_geometry() {
+        let mut workspace = Workspace::new(Rectangle::new(900, 600));
+
+        workspace.grid_layout(1, 3).unwrap();
+        workspace.history_depth = 8;
+
+        let columns = workspace.regions.clone();
+
+        workspace.maximize_region(1, 0.5).unwrap();
+        workspace.undo().unwrap();
+
+        assert_eq!(workspace.regions, columns);
+
+        workspace
+            .resize_region_by_index(0, &Resize::Right(100))
+            .unwrap();
+
+        let resized = workspace.regions.clone();
+
+        assert_eq!(workspace.unmaximize(), Err(ErrorKind::NoHistory));
+        assert_eq!(workspace.regions, resized);
+
+        workspace.undo().unwrap();
+        workspace.maximize_region(1, 0.5).unwrap();
+        workspace.undo().unwrap();
+        workspace.redo().unwrap();
+        workspace.unmaximize().unwrap();
+
+        assert_eq!(workspace.regions, columns);
+    }
+
+    #[test]
+    fn shared_edge_segment_clipped() {
+        let mut workspace = split_workspace();
+
+        workspace.create_region(1, &Direction::Down).unwrap();
+
+        assert_eq!(
+            workspace.shared_edge_segment(0, 2, &Direction::Right),
+            Some((Position::new(500, 250), Position::new(500, 500)))
+        );
+        assert_eq!(
+            workspace.shared_edge_segment(1, 0, &Direction::Left),
+            Some((Position::new(500, 0), Position::new(500, 250)))
+        );
+        assert_eq!(workspace.shared_edge_segment(1, 2, &Direction::Right), None);
+        assert_eq!(workspace.shared_edge_segment(0, 3, &Direction::Right), None);
+    }
+
+    #[test]
+    fn remove_monitor_reassigns_to_fallback() {
+        let mut manager = manager_with_workspaces(1);
+        let monitor = manager.add_monitor(Rectangle::new(800, 600), Position::new(1000, 0));
+        let monitor = *manager.monitors.get(monitor).unwrap();
+
+        manager.create_workspace(&monitor).unwrap();
+
+        assert_eq!(manager.remove_monitor(0, 0), Err(ErrorKind::InvalidMonitor));
+        assert_eq!(manager.remove_monitor(2, 0), Err(ErrorKind::UnknownMonitor));
+        assert_eq!(manager.remove_monitor(0, 2), Err(ErrorKind::UnknownMonitor));
+
+        manager.remove_monitor(0, 1).unwrap();
+
+        assert_eq!(manager.monitors, vec![monitor]);
+
+        for workspace in &manager.workspaces {
+            assert_eq!(workspace.monitor, Some(0));
+            assert_eq!(workspace.size, Rectangle::new(800, 600));
+            assert!(workspace.assert_tiles_exactly().is_ok());
+        }
+    }
+
+    #[test]
+    fn region_normalized_round_trip() {
+        let mut workspace = three_columns();
+        let layout = workspace.regions.clone();
+        let (x, y, w, h) = workspace.region_normalized(1).unwrap();
+
+        assert!((x - 1.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!((y, h), (0.0, 1.0));
+        assert!((w - 1.0 / 3.0).abs() < f64::EPSILON);
+
+        for index in 0..3 {
+            let normalized = workspace.region_normalized(index).unwrap();
+
+            workspace.set_region_normalized(index, normalized).unwrap();
+        }
+
+        assert_eq!(workspace.regions, layout);
+    }
+
+    #[test]
+    fn rotate_layout_stacks_columns() {
+        let mut workspace = three_columns();
+
+        workspace.rotate_layout(Rectangle::new(600, 900)).unwrap();
+
+        assert_eq!(workspace.size, Rectangle::new(600, 900));
+
+        for (index, region) in workspace.regions.iter().enumerate() {
+            assert_eq!(region.pos, Position::new(0, index as i64 * 300));
+            assert_eq!(region.size, Rectangle::new(600, 300));
+        }
+
+        assert!(workspace.assert_tiles_exactly().is_ok());
+    }
+
+    #[test]
+    fn reconcile_edges_closes_one_pixel_gap() {
+        let mut workspace = split_workspace();
+        let region = workspace.region_mut(1).unwrap();
+
+        region.pos.x = 501;
+        region.size.w = 499;
+
+        assert!(workspace.assert_tiles_exactly().is_err());
+
+        workspace.reconcile_edges().unwrap();
+
+        assert_eq!(workspace.region(1).unwrap().left(), 500);
+        assert_eq!(workspace.region(1).unwrap().right(), 1000);
+        assert!(workspace.assert_tiles_exactly().is_ok());
+    }
+
+    #[test]
+    fn region_split_halves_tile_exactly() {
+        let region = Region::new(Rectangle::new(1001, 500), Position::new(0, 0), false);
+        let (left, right) = region.split(&Direction::Right, 0.5).unwrap();
+
+        assert_eq!(left.size, Rectangle::new(501, 500));
+        assert_eq!(right.size, Rectangle::new(500, 500));
+        assert_eq!(left.right(), right.left());
+        assert_eq!(left.area() + right.area(), region.area());
+
+        let (bottom, top) = region.split(&Direction::Up, 0.5).unwrap();
+
+        assert_eq!(top.pos, region.pos);
+        assert_eq!(top.bottom(), bottom.top());
+        assert_eq!(bottom.bottom(), region.bottom());
+        assert_eq!(
+            region.split(&Direction::Up, 1.0),
+            Err(ErrorKind::InvalidRegion)
+        );
+    }
+
+    #[test]
+    fn group_as_tabs_shares_geometry() {
+        let mut workspace = Workspace::new(Rectangle::new(900, 600));
+
+        workspace.grid_layout(1, 3).unwrap();
+
+        let group = workspace.group_as_tabs(&[0, 1, 2]).unwrap();
+
+        for region in &workspace.regions {
+            assert_eq!(region.tab_group, Some(group));
+            assert_eq!(region.size, Rectangle::new(900, 600));
+            assert_eq!(region.pos, Position::new(0, 0));
+        }
+
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn group_as_tabs_resizes_together() {
+        let mut workspace = Workspace::new(Rectangle::new(900, 600));
+
+        workspace.grid_layout(1, 3).unwrap();
+        workspace.group_as_tabs(&[1, 2]).unwrap();
+
+        assert_eq!(workspace.tiled_regions().count(), 2);
+
+        workspace
+            .resize_region_by_index(2, &Resize::Left(100))
+            .unwrap();
+
+        assert_eq!(workspace.region(0).unwrap().size.w, 400);
+
+        for index in [1, 2] {
+            let region = workspace.region(index).unwrap();
+
+            assert_eq!(region.left(), 400);
+            assert_eq!(region.size.w, 500);
+        }
+
+        assert!(workspace.layout_issues().is_empty());
+    }
+
+    #[test]
+    fn group_as_tabs_rejects_grouped() {
+        let mut workspace = Workspace::new(Rectangle::new(900, 600));
+
+        workspace.grid_layout(1, 3).unwrap();
+        workspace.group_as_tabs(&[0, 1]).unwrap();
+
+        let before = workspace.clone();
+
+        assert_eq!(
+            workspace.group_as_tabs(&[1, 2]),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(workspace, before);
+        assert!(workspace.layout_issues().is_empty());
+    }
+
+    #[test]
+    fn resize_affects_matches_resize() {
+        for resize in [
+            Resize::Right(100),
+            Resize::Bottom(-50),
+            Resize::BottomRight(100, 50),
+        ] {
+            let mut workspace = quad_workspace();
+            let predicted = workspace.resize_affects(0, &resize);
+            let before = workspace.regions.clone();
+
+            workspace.resize_region_by_index(0, &resize).unwrap();
+
+            let changed = (1..before.len())
+                .filter(|index| before.get(*index) != workspace.regions.get(*index))
+                .collect::<Vec<_>>();
+
+            assert!(!changed.is_empty());
+            assert_eq!(predicted, changed);
+        }
+
+        assert!(quad_workspace()
+            .resize_affects(0, &Resize::Right(1000))
+            .is_empty());
+    }
+
+    #[test]
+    fn geometry_in_sets() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let positions = [
+            Position::new(0, 0),
+            Position::new(-5, 10),
+            Position::new(0, 0),
+        ];
+        let set = positions.into_iter().collect::<HashSet<_>>();
+
+        assert_eq!(set.len(), 2);
+        assert!(set.contains(&Position::new(-5, 10)));
+
+        let rectangles = [
+            Rectangle::new(20, 10),
+            Rectangle::new(10, 20),
+            Rectangle::new(20, 10),
+        ];
+
+        assert_eq!(rectangles.into_iter().collect::<HashSet<_>>().len(), 2);
+        assert_eq!(
+            rectangles.into_iter().collect::<BTreeSet<_>>().first(),
+            Some(&Rectangle::new(10, 20))
+        );
+        assert_eq!(
+            Direction::all()
+                .into_iter()
+                .chain(Direction::all())
+                .collect::<HashSet<_>>()
+                .len(),
+            4
+        );
+    }
+
+    #[test]
+    fn largest_and_smallest_regions() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.split_region(0, &Direction::Right, 0.6).unwrap();
+        workspace.create_region(1, &Direction::Down).unwrap();
+        workspace.regions.extend([Region::new(
+            Rectangle::new(20, 20),
+            Position::new(0, 0),
+            true,
+        )]);
+
+        assert_eq!(workspace.largest_region(), Some(0));
+        assert_eq!(workspace.smallest_region(), Some(1));
+
+        workspace.regions.truncate(1);
+        workspace.regions.extend([Region::new(
+            Rectangle::new(400, 500),
+            Position::new(0, 0),
+            false,
+        )]);
+
+        assert_eq!(workspace.largest_region(), Some(0));
+        assert_eq!(workspace.smallest_region(), Some(0));
+        assert_eq!(
+            Workspace::empty(Rectangle::new(1000, 500)).largest_region(),
+            None
+        );
+    }
+
+    #[test]
+    fn manager_move_region_scales_across_sizes() {
+        let mut manager = manager_with_workspaces(1);
+        let monitor = manager.add_monitor(Rectangle::new(2000, 1000), Position::new(1000, 0));
+        let monitor = *manager.monitors.get(monitor).unwrap();
+
+        manager.create_workspace(&monitor).unwrap();
+        manager
+            .workspaces
+            .get_mut(0)
+            .unwrap()
+            .create_region(0, &Direction::Right)
+            .unwrap();
+
+        let index = manager.move_region(0, 1, 1).unwrap();
+        let moved = manager.workspaces[1].region(index).unwrap();
+
+        assert!(moved.float);
+        assert_eq!(moved.pos, Position::new(1000, 0));
+        assert_eq!(moved.size, Rectangle::new(1000, 1000));
+        assert_eq!(manager.workspaces[0].region_count(), 1);
+        assert_eq!(manager.workspaces[0].assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn manager_move_region_records_and_raises() {
+        let mut manager = manager_with_workspaces(2);
+        let source = manager.workspaces.get_mut(0).unwrap();
+
+        source.history_depth = 5;
+        source.create_region(0, &Direction::Right).unwrap();
+
+        let target = manager.workspaces.get_mut(1).unwrap();
+
+        target.history_depth = 5;
+        target.struts = (100, 0, 0, 0);
+        target.place_float(Rectangle::new(200, 200)).unwrap();
+        target.place_float(Rectangle::new(200, 200)).unwrap();
+
+        let index = manager.move_region(0, 1, 1).unwrap();
+        let target = manager.workspaces.get_mut(1).unwrap();
+        let moved = *target.region(index).unwrap();
+
+        assert_eq!(moved.pos, Position::new(500, 100));
+        assert_eq!(moved.size, Rectangle::new(500, 400));
+        assert!(target
+            .floating_regions()
+            .all(|(other, float)| other == index || float.z < moved.z));
+
+        target.undo().unwrap();
+
+        assert_eq!(target.region_count(), 3);
+
+        let source = manager.workspaces.get_mut(0).unwrap();
+
+        source.undo().unwrap();
+
+        assert_eq!(source.region_count(), 2);
+    }
+
+    #[test]
+    fn manager_move_region_from_zero_sized_bounds() {
+        let mut manager = manager_with_workspaces(2);
+
+        manager.workspaces.get_mut(0).unwrap().struts = (500, 0, 0, 0);
+
+        assert_eq!(manager.move_region(0, 0, 1), Err(ErrorKind::InvalidRegion));
+        assert_eq!(manager.workspaces[0].region_count(), 1);
+        assert_eq!(manager.workspaces[1].region_count(), 1);
+    }
+
+    #[test]
+    fn manager_move_region_within_workspace() {
+        let mut manager = manager_with_workspaces(1);
+
+        manager.workspaces.get_mut(0).unwrap().regions = split_workspace().regions;
+
+        let before = manager.clone();
+
+        assert_eq!(manager.move_region(0, 1, 0), Ok(1));
+        assert_eq!(manager, before);
+        assert_eq!(manager.move_region(0, 2, 0), Err(ErrorKind::UnknownRegion));
+    }
 
-//         self.workspaces.extend([Workspace::new(monitor.size)]);
+    #[test]
+    fn y_axis_convention_round_trip() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
 
-//         Ok(self.workspaces.last().unwrap())
-//     }
+        workspace.split_region(0, &Direction::Down, 0.2).unwrap();
 
-//     pub fn remove_workspace(&mut self, workspace: &Workspace) -> Result<()> {
-//         self.workspaces.remove(self.workspace_index(workspace)?);
+        let up = workspace.region_in_convention(1, YAxis::Up).unwrap();
 
-//         Ok(())
-//     }
+        assert_eq!(up.pos, Position::new(0, 0));
+        assert_eq!(
+            workspace.region_in_convention(0, YAxis::Up).unwrap().pos.y,
+            100
+        );
+        assert_eq!(
+            workspace.region_in_convention(1, YAxis::Down),
+            workspace.region(1).copied()
+        );
 
-//     pub fn move_workspace(&mut self, workspace: &mut Workspace, monitor: &Monitor) -> Result<()> {
-//         workspace.resize(monitor.size)
-//     }
+        for index in 0..2 {
+            let region = workspace.region_in_convention(index, YAxis::Up).unwrap();
 
-//     fn workspace_index(&self, workspace: &Workspace) -> Result<usize> {
-//         self.workspaces
-//             .iter()
-//             .position(|x| x == workspace)
-//             .ok_or(ErrorKind::UnknownWorkspace)
-//     }
-// }
+            assert_eq!(
+                workspace.region_from_convention(region, YAxis::Up),
+                *workspace.region(index).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn regions_touching_corner_of_grid() {
+        let workspace = quad_workspace();
+
+        assert_eq!(
+            workspace.regions_touching(0),
+            vec![(2, Direction::Down), (1, Direction::Right)]
+        );
+        assert_eq!(
+            workspace.regions_touching(3),
+            vec![(1, Direction::Up), (2, Direction::Left)]
+        );
+        assert!(workspace.regions_touching(4).is_empty());
+    }
+
+    #[test]
+    fn degenerate_workspace_sizes() {
+        let mut manager = Manager::default();
+
+        for size in [Rectangle::new(0, 0), Rectangle::new(10, 10)] {
+            assert_eq!(Workspace::try_new(size), Err(ErrorKind::InvalidRegion));
+
+            let monitor = manager.add_monitor(size, Position::new(0, 0));
+            let monitor = *manager.monitors.get(monitor).unwrap();
+
+            assert_eq!(
+                manager.create_workspace(&monitor),
+                Err(ErrorKind::InvalidRegion)
+            );
+        }
+
+        assert!(manager.workspaces.is_empty());
+        assert!(Workspace::try_new(MIN_REGION_SIZE).is_ok());
+    }
+
+    #[test]
+    fn assert_tiles_exactly_detects_gap() {
+        let mut workspace = quad_workspace();
+
+        assert!(workspace.assert_tiles_exactly().is_ok());
+
+        workspace.region_mut(3).unwrap().size.w -= 1;
+
+        assert!(workspace.validate().is_ok());
+        assert_eq!(
+            workspace.assert_tiles_exactly(),
+            Err(ErrorKind::InvalidRegion)
+        );
+    }
+
+    #[test]
+    fn grow_against_major_leaves_minor_neighbor() {
+        let mut workspace = split_workspace();
+
+        workspace.split_region(1, &Direction::Down, 0.3).unwrap();
+
+        let minor = *workspace.region(2).unwrap();
+
+        workspace
+            .grow_against_major(0, &Direction::Right, 100)
+            .unwrap();
+
+        assert_eq!(workspace.region(0).unwrap().right(), 600);
+        assert_eq!(workspace.region(1).unwrap().left(), 600);
+        assert_eq!(workspace.region(1).unwrap().size.w, 400);
+        assert_eq!(*workspace.region(2).unwrap(), minor);
+        assert_eq!(workspace.layout_issues(), vec![LayoutIssue::Overlap(0, 2)]);
+        assert_eq!(
+            workspace.grow_against_major(1, &Direction::Right, 10),
+            Err(ErrorKind::NoAdjacentRegions)
+        );
+    }
+
+    #[test]
+    fn clear_matches_fresh_workspace() {
+        let mut workspace = quad_workspace();
+
+        workspace.place_float(Rectangle::new(200, 200)).unwrap();
+        workspace.focused_region = Some(3);
+        workspace.maximize_region(0, 0.8).unwrap();
+        workspace.clear();
+
+        assert_eq!(workspace, Workspace::new(Rectangle::new(1000, 500)));
+    }
+
+    #[test]
+    fn raise_and_lower_change_region_at() {
+        let mut workspace = stacked_floats();
+        let overlap = workspace.region(3).unwrap().pos;
+
+        assert_eq!(workspace.region_at(overlap), Some(3));
+
+        workspace.raise_region(1).unwrap();
+
+        assert_eq!(workspace.region_at(overlap), Some(1));
+
+        workspace.lower_region(1).unwrap();
+
+        assert_eq!(workspace.region_at(overlap), Some(3));
+
+        workspace.lower_region(3).unwrap();
+
+        assert_eq!(workspace.region_at(overlap), Some(2));
+    }
+
+    #[test]
+    fn place_float_stacks_on_top() {
+        let mut workspace = stacked_floats();
+
+        workspace.raise_region(1).unwrap();
+        workspace.raise_region(2).unwrap();
+
+        let index = workspace.place_float(Rectangle::new(200, 200)).unwrap();
+        let region = workspace.region(index).unwrap();
+
+        assert!(workspace
+            .floating_regions()
+            .all(|(other, float)| other == index || float.z < region.z));
+        assert_eq!(workspace.region_at(region.center()), Some(index));
+    }
+
+    #[test]
+    fn focus_monitor_horizontal_row() {
+        let mut manager = Manager::default();
+
+        for x in [0, 1000, 2000] {
+            manager.add_monitor(Rectangle::new(1000, 500), Position::new(x, 0));
+        }
+
+        assert_eq!(manager.focus_monitor(0, &Direction::Right), Ok(1));
+        assert_eq!(manager.focus_monitor(1, &Direction::Right), Ok(2));
+        assert_eq!(manager.focus_monitor(1, &Direction::Left), Ok(0));
+        assert_eq!(manager.focus_monitor(2, &Direction::Left), Ok(1));
+        assert_eq!(
+            manager.focus_monitor(2, &Direction::Right),
+            Err(ErrorKind::NoAdjacentRegions)
+        );
+        assert_eq!(
+            manager.focus_monitor(0, &Direction::Up),
+            Err(ErrorKind::NoAdjacentRegions)
+        );
+        assert_eq!(
+            manager.focus_monitor(3, &Direction::Left),
+            Err(ErrorKind::UnknownMonitor)
+        );
+    }
+
+    #[test]
+    fn focus_monitor_skips_diagonal() {
+        let mut manager = Manager::default();
+
+        manager.add_monitor(Rectangle::new(1000, 500), Position::new(0, 0));
+        manager.add_monitor(Rectangle::new(1000, 500), Position::new(1000, -500));
+        manager.add_monitor(Rectangle::new(1000, 500), Position::new(0, -500));
+
+        assert_eq!(
+            manager.focus_monitor(0, &Direction::Right),
+            Err(ErrorKind::NoAdjacentRegions)
+        );
+        assert_eq!(manager.focus_monitor(0, &Direction::Up), Ok(2));
+        assert_eq!(manager.focus_monitor(2, &Direction::Right), Ok(1));
+
+        manager.add_monitor(Rectangle::new(1000, 500), Position::new(3000, 100));
+
+        assert_eq!(manager.focus_monitor(0, &Direction::Right), Ok(3));
+    }
+
+    #[test]
+    fn clamp_to_off_top_right() {
+        let bounds = Region::new(Rectangle::new(1000, 500), Position::new(0, 0), false);
+        let mut region = Region::new(Rectangle::new(200, 100), Position::new(5000, -3000), true);
+
+        region.clamp_to(&bounds, 50);
+
+        assert_eq!(region.pos, Position::new(950, -50));
+        assert_eq!(region.size, Rectangle::new(200, 100));
+
+        region.pos = Position::new(5000, -3000);
+        region.clamp_to(&bounds, 500);
+
+        assert_eq!(region.pos, Position::new(800, 0));
+
+        let mut large = Region::new(Rectangle::new(2000, 1000), Position::new(5000, -3000), true);
+
+        large.clamp_to(&bounds, 1000);
+
+        assert_eq!(large.pos, Position::new(0, -500));
+    }
+
+    #[test]
+    fn top_strut_offsets_tiled_regions() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.struts = (30, 0, 0, 0);
+
+        assert_eq!(workspace.bounds().pos, Position::new(0, 30));
+        assert_eq!(workspace.bounds().size, Rectangle::new(1000, 470));
+
+        workspace.grid_layout(1, 2).unwrap();
+
+        for (_, region) in workspace.tiled_regions() {
+            assert_eq!(region.top(), 30);
+            assert_eq!(region.size.h, 470);
+        }
+
+        let float = workspace.place_float(Rectangle::new(200, 200)).unwrap();
+
+        assert!(workspace.region(float).unwrap().top() >= 30);
+        assert!(workspace.assert_tiles_exactly().is_ok());
+    }
+
+    #[test]
+    fn same_geometry_ignores_float() {
+        let tiled = Region::new(Rectangle::new(200, 100), Position::new(10, 20), false);
+        let float = Region::new(Rectangle::new(200, 100), Position::new(10, 20), true);
+
+        assert_ne!(tiled, float);
+        assert!(tiled.same_geometry(&float));
+        assert!(float.same_geometry(&tiled));
+        assert!(!tiled.same_geometry(&float.translated(1, 0)));
+    }
+
+    #[test]
+    fn apply_layout_round_trip() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+
+        workspace.grid_layout(2, 2).unwrap();
+        workspace.place_float(Rectangle::new(200, 200)).unwrap();
+
+        let expected = workspace.clone();
+        let infos = workspace.describe();
+
+        workspace.clear();
+        workspace.apply_layout(&infos).unwrap();
+
+        assert_eq!(workspace, expected);
+    }
+
+    #[test]
+    fn apply_layout_rejects_gaps_and_bad_indices() {
+        let mut workspace = split_workspace();
+        let infos = workspace.describe();
+        let before = workspace.clone();
+
+        assert_eq!(
+            workspace.apply_layout(&infos[..1]),
+            Err(ErrorKind::InvalidRegion)
+        );
+
+        let mut duplicate = infos.clone();
+
+        duplicate[1].index = 0;
+
+        assert_eq!(
+            workspace.apply_layout(&duplicate),
+            Err(ErrorKind::InvalidRegion)
+        );
+
+        let mut missing = infos.clone();
+
+        missing[1].index = 2;
+
+        assert_eq!(
+            workspace.apply_layout(&missing),
+            Err(ErrorKind::InvalidRegion)
+        );
+        assert_eq!(workspace, before);
+    }
+
+    #[test]
+    fn resize_region_percent_of_width() {
+        let mut workspace = split_workspace();
+
+        workspace
+            .resize_region_percent(0, &Direction::Right, 10.0)
+            .unwrap();
+
+        assert_eq!(workspace.region(0).unwrap().right(), 600);
+        assert_eq!(workspace.region(1).unwrap().left(), 600);
+
+        workspace
+            .resize_region_percent(0, &Direction::Right, -20.0)
+            .unwrap();
+
+        assert_eq!(workspace.region(0).unwrap().right(), 400);
+        assert!(workspace.assert_tiles_exactly().is_ok());
+    }
+
+    #[test]
+    fn uncovered_areas_l_shape() {
+        let mut workspace = Workspace::empty(Rectangle::new(1000, 500));
+
+        workspace.regions.extend([Region::new(
+            Rectangle::new(500, 250),
+            Position::new(0, 0),
+            false,
+        )]);
+
+        assert_eq!(
+            workspace.uncovered_areas(),
+            vec![
+                Region::new(Rectangle::new(500, 250), Position::new(500, 0), false),
+                Region::new(Rectangle::new(1000, 250), Position::new(0, 250), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn nearest_edge_at_shared_edge() {
+        let workspace = split_workspace();
+
+        assert_eq!(
+            workspace.nearest_edge(Position::new(497, 200), 5),
+            Some((0, Direction::Right))
+        );
+        assert_eq!(
+            workspace.nearest_edge(Position::new(503, 200), 5),
+            Some((1, Direction::Left))
+        );
+        assert_eq!(workspace.nearest_edge(Position::new(490, 200), 5), None);
+        assert_eq!(
+            workspace.nearest_edge(Position::new(498, 3), 5),
+            Some((0, Direction::Right))
+        );
+    }
+
+    #[test]
+    fn default_values() {
+        assert_eq!(Rectangle::default(), Rectangle::new(0, 0));
+        assert_eq!(Position::default(), Position::new(0, 0));
+        assert_eq!(
+            Region::default(),
+            Region::new(Rectangle::new(0, 0), Position::new(0, 0), false)
+        );
+        assert!(!Region::default().float);
+    }
+
+    #[test]
+    fn tile_float_tiles_exactly() {
+        let mut workspace = Workspace::new(Rectangle::new(1000, 500));
+        let float = workspace.place_float(Rectangle::new(200, 200)).unwrap();
+
+        workspace.tile_float(float, 0, &Direction::Right).unwrap();
+
+        let region = workspace.region(float).unwrap();
+
+        assert!(!region.float);
+        assert_eq!(region.pos, Position::new(500, 0));
+        assert_eq!(region.size, Rectangle::new(500, 500));
+        assert_eq!(workspace.region(0).unwrap().size, Rectangle::new(500, 500));
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn tile_float_leaves_tab_group() {
+        let mut workspace = split_workspace();
+        let group = workspace.group_as_tabs(&[0, 1]).unwrap();
+        let float = workspace.place_float(Rectangle::new(200, 200)).unwrap();
+
+        workspace.regions.get_mut(float).unwrap().tab_group = Some(group);
+        workspace.tile_float(float, 0, &Direction::Down).unwrap();
+
+        let region = workspace.region(float).unwrap();
+
+        assert_eq!(region.tab_group, None);
+        assert_eq!(region.pos, Position::new(0, 250));
+        assert_eq!(region.size, Rectangle::new(1000, 250));
+        assert_eq!(workspace.assert_tiles_exactly(), Ok(()));
+    }
+
+    #[test]
+    fn float_region_backfills_and_centers() {
+        let mut workspace = split_workspace();
+
+        workspace.float_region(1).unwrap();
+
+        let float = *workspace.region(1).unwrap();
+
+        assert!(float.float);
+        assert_eq!(float.size, Rectangle::new(600, 300));
+        assert_eq!(float.pos, Position::new(200, 100));
+        assert_eq!(float.center(), workspace.bounds().center());
+        assert_eq!(workspace.region(0).unwrap().size, Rectangle::new(1000, 500));
+        assert!(workspace.assert_tiles_exactly().is_ok());
+        assert_eq!(workspace.float_region(1), Err(ErrorKind::InvalidRegion));
+    }
+
+    #[test]
+    fn floating_and_tiled_counts_sum() {
+        let mut workspace = float_beside_split();
+
+        workspace.place_float(Rectangle::new(100, 100)).unwrap();
+
+        assert_eq!(workspace.count_floating(), 2);
+        assert_eq!(workspace.count_tiled(), 2);
+        assert_eq!(
+            workspace.count_floating() + workspace.count_tiled(),
+            workspace.region_count()
+        );
+    }
+
+    #[test]
+    fn manager_find_region_in_second_workspace() {
+        let mut manager = manager_with_workspaces(2);
+        let workspace = manager.workspaces.get_mut(1).unwrap();
+
+        workspace.create_region(0, &Direction::Down).unwrap();
+
+        let region = *workspace.region(1).unwrap();
+
+        assert_eq!(manager.find_region(&region), Some((1, 1)));
+        assert_eq!(
+            manager.find_region(manager.workspaces.first().unwrap().region(0).unwrap()),
+            Some((0, 0))
+        );
+        assert_eq!(manager.find_region(&region.translated(1, 0)), None);
+    }
+}